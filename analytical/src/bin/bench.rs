@@ -0,0 +1,135 @@
+use analytical::{GameStatus, Priorities, Simulator, MAX_SCORE};
+
+const HISTOGRAM_WIDTH: usize = 50;
+
+fn usage() -> ! {
+    eprintln!("Usage: analytical-bench <players> [games] [first seed] [priorities.json]");
+    std::process::exit(1);
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let players: usize = match args.next().map(|a| a.parse()) {
+        Some(Ok(p)) => p,
+        _ => usage(),
+    };
+    let games: u64 = match args.next().map(|a| a.parse()) {
+        Some(Ok(g)) => g,
+        Some(Err(_)) => usage(),
+        None => 1000,
+    };
+    let first_seed: u64 = match args.next().map(|a| a.parse()) {
+        Some(Ok(s)) => s,
+        Some(Err(_)) => usage(),
+        None => 0,
+    };
+    let priorities = match args.next() {
+        Some(path) => load_priorities(&path),
+        None => Priorities::default(),
+    };
+
+    let mut scores = Vec::new();
+    let mut perfect = 0;
+    let mut strikeouts = 0;
+    let mut clues_wasted = 0;
+    let mut illegal = 0;
+    let mut crashed = 0;
+    for seed in first_seed..first_seed + games {
+        let sim = match Simulator::with_priorities(players, seed, priorities) {
+            Ok(sim) => sim,
+            Err(e) => {
+                eprintln!("Couldn't set up the game: {e}");
+                std::process::exit(1);
+            }
+        };
+        // A Brain that panics shouldn't take the rest of the run down with it. The
+        // default hook still prints where it panicked, and we note which seed it was.
+        let record = match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| sim.run())) {
+            Ok(Ok(record)) => record,
+            Ok(Err(e)) => {
+                eprintln!("Seed {seed} made an illegal move: {e}");
+                illegal += 1;
+                continue;
+            }
+            Err(payload) => {
+                let message = payload
+                    .downcast_ref::<&str>()
+                    .copied()
+                    .or_else(|| payload.downcast_ref::<String>().map(|s| s.as_str()))
+                    .unwrap_or("unknown panic");
+                eprintln!("Seed {seed} crashed: {message}");
+                crashed += 1;
+                continue;
+            }
+        };
+        match record.status() {
+            GameStatus::Won(_) => perfect += 1,
+            GameStatus::LostToStrikes(_) => strikeouts += 1,
+            _ => {}
+        }
+        clues_wasted += record.clues_wasted();
+        scores.push(record.score());
+    }
+
+    println!(
+        "Games: {games} ({players} players, seeds {first_seed}..{})",
+        first_seed + games
+    );
+    println!("Illegal moves: {illegal}");
+    println!("Crashed: {crashed}");
+    if scores.is_empty() {
+        return;
+    }
+
+    let finished = scores.len() as f64;
+    scores.sort();
+    let mean = scores.iter().sum::<usize>() as f64 / finished;
+    let median = if scores.len() % 2 == 0 {
+        (scores[scores.len() / 2 - 1] + scores[scores.len() / 2]) as f64 / 2.0
+    } else {
+        scores[scores.len() / 2] as f64
+    };
+    println!("Mean score: {mean:.2}");
+    println!("Median score: {median:.1}");
+    println!("Perfect games: {:.1}%", perfect as f64 / finished * 100.0);
+    println!("Strikeouts: {:.1}%", strikeouts as f64 / finished * 100.0);
+    println!(
+        "Average clues wasted: {:.2}",
+        clues_wasted as f64 / finished
+    );
+
+    let mut histogram = [0; MAX_SCORE + 1];
+    for score in &scores {
+        histogram[*score] += 1;
+    }
+    let most = *histogram.iter().max().unwrap();
+    println!("Scores:");
+    for (score, count) in histogram.iter().enumerate() {
+        let bar = "#".repeat(count * HISTOGRAM_WIDTH / most);
+        println!("{score:>3} | {bar} {count}");
+    }
+}
+
+#[cfg(all(feature = "serde", feature = "serde_json"))]
+fn load_priorities(path: &str) -> Priorities {
+    let json = match std::fs::read_to_string(path) {
+        Ok(json) => json,
+        Err(e) => {
+            eprintln!("Couldn't read {path}: {e}");
+            std::process::exit(1);
+        }
+    };
+    match Priorities::from_json(&json) {
+        Ok(priorities) => priorities,
+        Err(e) => {
+            eprintln!("Invalid priorities in {path}: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(not(all(feature = "serde", feature = "serde_json")))]
+fn load_priorities(_: &str) -> Priorities {
+    eprintln!("Loading priorities needs the serde and serde_json features");
+    std::process::exit(1);
+}
@@ -0,0 +1,38 @@
+use analytical::Simulator;
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let players: usize = match args.next().map(|p| p.parse()) {
+        Some(Ok(p)) => p,
+        _ => {
+            eprintln!("Usage: analytical-sim <players> [seed]");
+            std::process::exit(1);
+        }
+    };
+    let seed: u64 = match args.next().map(|s| s.parse()) {
+        Some(Ok(s)) => s,
+        Some(Err(e)) => {
+            eprintln!("Invalid seed: {e}");
+            std::process::exit(1);
+        }
+        None => 0,
+    };
+
+    let record = match Simulator::new(players, seed).map(|s| s.run()) {
+        Ok(Ok(record)) => record,
+        Ok(Err(e)) => {
+            eprintln!("A player made an illegal move: {e}");
+            std::process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Couldn't set up the game: {e}");
+            std::process::exit(1);
+        }
+    };
+
+    for (turn, (seat, action, outcome)) in record.turns().iter().enumerate() {
+        println!("{turn:>3}: Player {seat}: {action:?} -> {outcome:?}");
+    }
+    println!("{:?}", record.status());
+    println!("Score: {}", record.score());
+}
@@ -0,0 +1,257 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    rules::{copies, hand_size, MAX_HINTS, MAX_PLAYERS, MAX_STRIKES, MIN_PLAYERS},
+    CardId, Color, GameState, PlayerHand, TeammateHand,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildError {
+    WrongPlayerCount(usize),
+    MissingPlayerHand,
+    HandTooBig(usize),
+    DuplicateCard(CardId),
+    TooManyCopies(Color, usize),
+    InvalidNumber(usize),
+    BrokenStack(Color),
+    TooManyHints(usize),
+    TooManyStrikes(usize),
+    NoSuchPlayer(usize),
+    TooManyTurnsLeft(usize),
+    FinalRoundWithCardsLeft,
+}
+
+impl std::fmt::Display for BuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BuildError::WrongPlayerCount(n) => write!(
+                f,
+                "{n} players isn't between {MIN_PLAYERS} and {MAX_PLAYERS}"
+            ),
+            BuildError::MissingPlayerHand => write!(f, "no hand was given for the player"),
+            BuildError::HandTooBig(seat) => write!(f, "player {seat} has too many cards"),
+            BuildError::DuplicateCard(id) => write!(f, "card {id:?} appears more than once"),
+            BuildError::TooManyCopies(c, n) => write!(f, "there are too many copies of {c}{n}"),
+            BuildError::InvalidNumber(n) => write!(f, "{n} is not a valid card number"),
+            BuildError::BrokenStack(c) => write!(f, "the {c} stack isn't played in order"),
+            BuildError::TooManyHints(n) => {
+                write!(f, "{n} hints is more than the maximum of {MAX_HINTS}")
+            }
+            BuildError::TooManyStrikes(n) => {
+                write!(f, "{n} strikes would have already ended the game")
+            }
+            BuildError::NoSuchPlayer(p) => write!(f, "there is no player {p}"),
+            BuildError::TooManyTurnsLeft(n) => {
+                write!(f, "{n} turns left is more than one for each player")
+            }
+            BuildError::FinalRoundWithCardsLeft => {
+                write!(
+                    f,
+                    "the final round can't start while there are cards to draw"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+pub struct GameStateBuilder {
+    team_hands: Vec<TeammateHand>,
+    player_hand: Option<PlayerHand>,
+    discarded: Vec<(CardId, Color, usize)>,
+    played: Vec<(CardId, Color, usize)>,
+    hint_count: usize,
+    strikes: usize,
+    turn_counter: usize,
+    current_player: usize,
+    deck: Vec<CardId>,
+    hidden: HashMap<CardId, (Color, usize)>,
+    turns_left: Option<usize>,
+}
+
+impl GameStateBuilder {
+    pub fn new() -> Self {
+        Self {
+            team_hands: Vec::new(),
+            player_hand: None,
+            discarded: Vec::new(),
+            played: Vec::new(),
+            hint_count: MAX_HINTS,
+            strikes: 0,
+            turn_counter: 0,
+            current_player: 0,
+            deck: Vec::new(),
+            hidden: HashMap::new(),
+            turns_left: None,
+        }
+    }
+
+    // Teammate hands are given in turn order, starting with the player after us
+    pub fn teammate_hand(mut self, hand: TeammateHand) -> Self {
+        self.team_hands.push(hand);
+        self
+    }
+
+    pub fn player_hand(mut self, hand: PlayerHand) -> Self {
+        self.player_hand = Some(hand);
+        self
+    }
+
+    pub fn played(mut self, id: CardId, color: Color, number: usize) -> Self {
+        self.played.push((id, color, number));
+        self
+    }
+
+    pub fn discarded(mut self, id: CardId, color: Color, number: usize) -> Self {
+        self.discarded.push((id, color, number));
+        self
+    }
+
+    pub fn hint_count(mut self, hint_count: usize) -> Self {
+        self.hint_count = hint_count;
+        self
+    }
+
+    pub fn strikes(mut self, strikes: usize) -> Self {
+        self.strikes = strikes;
+        self
+    }
+
+    pub fn turn(mut self, turn: usize) -> Self {
+        self.turn_counter = turn;
+        self
+    }
+
+    pub fn current_player(mut self, seat: usize) -> Self {
+        self.current_player = seat;
+        self
+    }
+
+    // The cards left to draw, the next card to be drawn is last
+    pub fn deck(mut self, deck: Vec<CardId>) -> Self {
+        self.deck = deck;
+        self
+    }
+
+    // Reveals a card we can't normally see, so the game can be driven locally
+    pub fn hidden(mut self, id: CardId, color: Color, number: usize) -> Self {
+        self.hidden.insert(id, (color, number));
+        self
+    }
+
+    // Puts the game in its final round, with this many turns to go once the deck is empty
+    pub fn turns_left(mut self, turns: usize) -> Self {
+        self.turns_left = Some(turns);
+        self
+    }
+
+    pub fn build(self) -> Result<GameState, BuildError> {
+        let players = self.team_hands.len() + 1;
+        if !(MIN_PLAYERS..=MAX_PLAYERS).contains(&players) {
+            return Err(BuildError::WrongPlayerCount(players));
+        }
+        let player_hand = self.player_hand.ok_or(BuildError::MissingPlayerHand)?;
+        // Hands only ever shrink once the deck runs out
+        if player_hand.len() > hand_size(players) {
+            return Err(BuildError::HandTooBig(0));
+        }
+        if let Some(seat) = self
+            .team_hands
+            .iter()
+            .position(|h| h.hand.len() > hand_size(players))
+        {
+            return Err(BuildError::HandTooBig(seat + 1));
+        }
+        if self.hint_count > MAX_HINTS {
+            return Err(BuildError::TooManyHints(self.hint_count));
+        }
+        if self.strikes >= MAX_STRIKES {
+            return Err(BuildError::TooManyStrikes(self.strikes));
+        }
+        if self.current_player >= players {
+            return Err(BuildError::NoSuchPlayer(self.current_player));
+        }
+        if let Some(turns) = self.turns_left {
+            if turns > players {
+                return Err(BuildError::TooManyTurnsLeft(turns));
+            }
+            if !self.deck.is_empty() {
+                return Err(BuildError::FinalRoundWithCardsLeft);
+            }
+        }
+
+        let mut seen = HashSet::new();
+        let ids = player_hand
+            .iter()
+            .map(|(id, _, _, _)| *id)
+            .chain(
+                self.team_hands
+                    .iter()
+                    .flat_map(|h| h.hand.iter().map(|(id, _, _, _)| *id)),
+            )
+            .chain(self.played.iter().map(|(id, _, _)| *id))
+            .chain(self.discarded.iter().map(|(id, _, _)| *id))
+            .chain(self.deck.iter().copied());
+        for id in ids {
+            if !seen.insert(id) {
+                return Err(BuildError::DuplicateCard(id));
+            }
+        }
+
+        // Cards we can see, plus any hidden ones we've been told about
+        let mut counts: HashMap<(Color, usize), usize> = HashMap::new();
+        let identities = self
+            .team_hands
+            .iter()
+            .flat_map(|h| h.hand.iter().map(|(_, c, n, _)| (*c, *n)))
+            .chain(self.played.iter().map(|(_, c, n)| (*c, *n)))
+            .chain(self.discarded.iter().map(|(_, c, n)| (*c, *n)))
+            .chain(self.hidden.values().copied());
+        for (color, number) in identities {
+            if !(1..=5).contains(&number) {
+                return Err(BuildError::InvalidNumber(number));
+            }
+            let count = counts.entry((color, number)).or_default();
+            *count += 1;
+            if *count > copies(number) {
+                return Err(BuildError::TooManyCopies(color, number));
+            }
+        }
+
+        let mut played: HashMap<Color, Vec<(CardId, usize)>> = HashMap::new();
+        for (id, color, number) in self.played {
+            let stack = played.entry(color).or_default();
+            if number != stack.len() + 1 {
+                return Err(BuildError::BrokenStack(color));
+            }
+            stack.push((id, number));
+        }
+
+        Ok(GameState {
+            team_hands: self.team_hands,
+            player_hand,
+            discarded: self.discarded,
+            played,
+            hint_count: self.hint_count,
+            strikes: self.strikes,
+            turn_counter: self.turn_counter,
+            current_player: self.current_player,
+            deck: self.deck,
+            hidden: self.hidden,
+            turns_left: self.turns_left,
+        })
+    }
+}
+
+impl Default for GameStateBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameState {
+    pub fn builder() -> GameStateBuilder {
+        GameStateBuilder::new()
+    }
+}
@@ -0,0 +1,475 @@
+use crate::{
+    apply_hint_to_card,
+    level1::{
+        identities, BluffThought, BluffedThought, FinesseThought, FinessedThought, FiveSaveThought,
+        FiveStallThought, PlayThought, PromptThought, PromptedThought, SaveThought, TwoSaveThought,
+    },
+    rules::copies,
+    CardId, Color, ColorFlags, Focus, GameEvent, GameState, Hint, NumberFlags, ThoughtGenerator,
+    ThoughtType,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClueKind {
+    Play,
+    FiveSave,
+    TwoSave,
+    CriticalSave,
+    // Re-touches cards that were already clued so they get played
+    Tempo,
+    // Re-touches cards to tell the player they aren't what they thought
+    Fix,
+    // Given when there's nothing better to do, rather than to get something played
+    Stall,
+}
+
+// Works through every clue it sees:
+// Gen 1: What kind of clue it was, and which card it was about
+// Gen 2: Why it was given
+// Gen 3: What we should do about it, if it was given to us
+pub struct ClueInterpreter {}
+
+impl ClueInterpreter {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for ClueInterpreter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ThoughtGenerator for ClueInterpreter {
+    fn observe(&mut self, event: &GameEvent, game_state: &GameState) -> Vec<ThoughtType> {
+        let GameEvent::Hint(giver, receiver, hint, touched) = event else {
+            return Vec::new();
+        };
+        let Some(focus) = focus_of_clue(game_state, *receiver, *hint, touched) else {
+            return Vec::new();
+        };
+
+        let kind = classify(game_state, *giver, *receiver, *hint, focus);
+
+        // A play clue on a card that can't be played yet needs the cards before it
+        // to come from somewhere first. Touched cards are looked for before
+        // anyone is bluffed, so it's only a bluff if nothing is being prompted.
+        if kind == ClueKind::Play {
+            let connections = connections_to_focus(game_state, *giver, *receiver, *hint, focus);
+            let prompted = connections
+                .iter()
+                .flatten()
+                .any(|c| matches!(c, Connection::Prompt(_, _)));
+            if !prompted {
+                if let Some(thought) = bluff(game_state, *giver, *receiver, *hint, focus) {
+                    return vec![thought];
+                }
+            }
+            if let Some(connections) = connections {
+                return respond_to_connections(*receiver, focus.id(), connections);
+            }
+        }
+
+        // Only clues given to us tell us what we should be doing
+        if *receiver != 0 {
+            return Vec::new();
+        }
+        respond(kind, *hint, focus.id(), game_state.turn_counter)
+            .into_iter()
+            .collect()
+    }
+}
+
+// Where a card connecting to a clued card is going to be played from, with the
+// seat holding it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connection {
+    // Already touched, so they'll play it once they see the clue
+    Prompt(usize, CardId),
+    // Blind played from the leftmost untouched slot
+    Finesse(usize, CardId),
+}
+
+impl Connection {
+    fn seat(&self) -> usize {
+        match self {
+            Connection::Prompt(seat, _) | Connection::Finesse(seat, _) => *seat,
+        }
+    }
+
+    fn id(&self) -> CardId {
+        match self {
+            Connection::Prompt(_, id) | Connection::Finesse(_, id) => *id,
+        }
+    }
+}
+
+// The cards that have to be played before the focused card can be, if it isn't
+// playable already
+fn connections_to_focus(
+    game_state: &GameState,
+    giver: usize,
+    receiver: usize,
+    hint: Hint,
+    focus: Focus,
+) -> Option<Vec<Connection>> {
+    if receiver != 0 {
+        let (_, color, number, _) = game_state.team_hands[receiver - 1].hand[focus.index()];
+        return connections(game_state, giver, receiver, focus.id(), color, number)
+            .filter(|c| !c.is_empty());
+    }
+
+    // We can't see our own card, but if everything the clue leaves it could be
+    // is reached through the same cards, that's what the clue was for
+    let (colors, numbers) = clued_identity(game_state, receiver, hint, focus);
+    if could_be_playable(game_state, colors, numbers) {
+        return None;
+    }
+    let mut chains = identities(colors, numbers).filter_map(|(color, number)| {
+        connections(game_state, giver, receiver, focus.id(), color, number)
+            .filter(|c| !c.is_empty())
+    });
+    let chain = chains.next()?;
+    chains.all(|other| other == chain).then_some(chain)
+}
+
+// Finds every card between the top of the stack and the clued card. Touched
+// cards that could connect get prompted first, then the finesse slots of
+// everyone but the giver and receiver are checked in turn order. Any card we
+// can't see has to be in our own hand.
+fn connections(
+    game_state: &GameState,
+    giver: usize,
+    receiver: usize,
+    hinted: CardId,
+    color: Color,
+    number: usize,
+) -> Option<Vec<Connection>> {
+    let players = game_state.players();
+    let next = game_state.played.get(&color).map_or(0, |s| s.len()) + 1;
+    let seats: Vec<_> = (1..players)
+        .map(|offset| (giver + offset) % players)
+        .collect();
+    let mut found: Vec<Connection> = Vec::new();
+
+    for rank in next..number {
+        let used: Vec<_> = found
+            .iter()
+            .map(|c| c.id())
+            .chain(std::iter::once(hinted))
+            .collect();
+        let could_be = |colors: ColorFlags, numbers: NumberFlags| {
+            colors.contains(color.into()) && numbers.contains(rank.into())
+        };
+
+        // They'll play the leftmost touched card that could be the one, so it
+        // only connects if that's actually it
+        let prompt = seats.iter().filter(|seat| **seat != 0).find_map(|seat| {
+            let hand = &game_state.team_hands[seat - 1];
+            let index = hand
+                .empathy
+                .iter()
+                .position(|(id, c, n, t)| *t && !used.contains(id) && could_be(*c, *n))?;
+            let (id, c, n, _) = hand.hand[index];
+            (c == color && n == rank).then_some(Connection::Prompt(*seat, id))
+        });
+        // We can't check our own cards, so we have to trust it's the right one
+        let self_prompt = || {
+            game_state
+                .player_hand
+                .iter()
+                .find(|(id, c, n, t)| *t && !used.contains(id) && could_be(*c, *n))
+                .map(|(id, _, _, _)| Connection::Prompt(0, *id))
+        };
+        let finesse = || {
+            seats
+                .iter()
+                .filter(|seat| **seat != 0 && **seat != receiver)
+                .find_map(|seat| {
+                    let hand = &game_state.team_hands[seat - 1];
+                    let (id, c, n, _) = hand
+                        .hand
+                        .iter()
+                        .find(|(id, _, _, t)| !t && !used.contains(id))?;
+                    (*c == color && *n == rank).then_some(Connection::Finesse(*seat, *id))
+                })
+        };
+
+        // We won't have given a clue that needs us to play into it
+        let connection = prompt
+            .or_else(|| (giver != 0).then(self_prompt).flatten())
+            .or_else(finesse);
+        let connection = match connection {
+            Some(connection) => connection,
+            // We can't be finessed by a clue we gave or were given
+            None if giver == 0 || receiver == 0 => return None,
+            // There's no point waiting on a card that can't be in our hand
+            None if unseen_copies(game_state, color, rank) == 0 => return None,
+            None => {
+                let (id, _, _, _) = game_state
+                    .player_hand
+                    .iter()
+                    .find(|(id, _, _, t)| !t && !used.contains(id))?;
+                Connection::Finesse(0, *id)
+            }
+        };
+        found.push(connection);
+    }
+
+    Some(found)
+}
+
+// Only the player right after the giver can be bluffed. They blind play their
+// finesse slot, which doesn't have to connect as long as the clued card is one-away.
+fn bluff(
+    game_state: &GameState,
+    giver: usize,
+    receiver: usize,
+    hint: Hint,
+    focus: Focus,
+) -> Option<ThoughtType> {
+    let bluffed = (giver + 1) % game_state.players();
+    if giver == 0 || bluffed == receiver {
+        return None;
+    }
+    let one_away = |color: Color, number: usize| {
+        game_state.played.get(&color).map_or(0, |s| s.len()) + 2 == number
+    };
+
+    if bluffed == 0 {
+        // Whether it's a finesse or a bluff, we only play the one card
+        let (_, color, number, _) = game_state.team_hands[receiver - 1].hand[focus.index()];
+        if !one_away(color, number) {
+            return None;
+        }
+        return match connections(game_state, giver, receiver, focus.id(), color, number)?[..] {
+            [Connection::Finesse(0, id)] => Some(ThoughtType::Bluffed(BluffedThought::new(id))),
+            _ => None,
+        };
+    }
+
+    let (id, color, number, _) = *game_state.team_hands[bluffed - 1]
+        .hand
+        .iter()
+        .find(|(_, _, _, t)| !t)?;
+    if !game_state.is_playable(color.into(), number.into()) {
+        return None;
+    }
+    // If their card could connect, it's a finesse instead
+    if receiver == 0 {
+        let (colors, numbers) = clued_identity(game_state, receiver, hint, focus);
+        let could_connect = colors.contains(color.into()) && numbers.contains((number + 1).into());
+        let any_one_away = colors.iter().any(|c| {
+            numbers
+                .iter()
+                .any(|n| match (Color::try_from(c), usize::try_from(n)) {
+                    (Ok(c), Ok(n)) => one_away(c, n),
+                    _ => false,
+                })
+        });
+        if could_connect || !any_one_away || could_be_playable(game_state, colors, numbers) {
+            return None;
+        }
+    } else {
+        let (_, c, n, _) = game_state.team_hands[receiver - 1].hand[focus.index()];
+        if !one_away(c, n) || (c == color && n == number + 1) {
+            return None;
+        }
+    }
+
+    Some(ThoughtType::Bluff(BluffThought::new(id, focus.id())))
+}
+
+// We play anything that's waiting on us, and otherwise keep track of the line
+// so we know when the clued card is ready
+fn respond_to_connections(
+    receiver: usize,
+    hinted: CardId,
+    connections: Vec<Connection>,
+) -> Vec<ThoughtType> {
+    let ours: Vec<_> = connections
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| c.seat() == 0)
+        .map(|(i, c)| {
+            // Whoever holds them, the cards before ours have to be played first
+            let earlier = connections[..i].iter().map(|c| c.id()).collect();
+            match c {
+                Connection::Prompt(_, id) => {
+                    ThoughtType::Prompted(PromptedThought::new(*id, earlier, hinted))
+                }
+                Connection::Finesse(_, id) => {
+                    ThoughtType::Finessed(FinessedThought::new(*id, earlier))
+                }
+            }
+        })
+        .collect();
+    // Unless the clued card is also ours, the holder takes it from here
+    if !ours.is_empty() && receiver != 0 {
+        return ours;
+    }
+
+    let ids = connections.iter().map(|c| c.id()).collect();
+    let line = if connections
+        .iter()
+        .all(|c| matches!(c, Connection::Prompt(_, _)))
+    {
+        ThoughtType::Prompt(PromptThought::new(ids, hinted))
+    } else {
+        ThoughtType::Finesse(FinesseThought::new(ids, hinted))
+    };
+    let mut thoughts = ours;
+    thoughts.push(line);
+    thoughts
+}
+
+// Which card a clue was about, seen from before the clue was given
+fn focus_of_clue(
+    game_state: &GameState,
+    receiver: usize,
+    hint: Hint,
+    touched: &[CardId],
+) -> Option<Focus> {
+    if receiver >= game_state.players() || touched.is_empty() {
+        return None;
+    }
+    if receiver == 0 {
+        let slots: Vec<_> = game_state
+            .player_hand
+            .iter()
+            .enumerate()
+            .filter(|(_, (id, _, _, _))| touched.contains(id))
+            .map(|(index, _)| index)
+            .collect();
+        return game_state.player_hand.determine_focus_for_hint(&slots).ok();
+    }
+
+    game_state.team_hands[receiver - 1]
+        .determine_focus_for_hint(hint)
+        .ok()
+}
+
+// What the focused card could be once the clue has been given. Our own card
+// is narrowed down by the clue, anyone else's we can just see.
+fn clued_identity(
+    game_state: &GameState,
+    receiver: usize,
+    hint: Hint,
+    focus: Focus,
+) -> (ColorFlags, NumberFlags) {
+    if receiver == 0 {
+        let mut card = game_state.player_hand[focus.index()];
+        apply_hint_to_card(&mut card, hint, true);
+        (card.1, card.2)
+    } else {
+        let (_, color, number, _) = game_state.team_hands[receiver - 1].hand[focus.index()];
+        (color.into(), number.into())
+    }
+}
+
+// Gen 1 and 2: what the clue was for, given what the focused card could be.
+// Cards on chop can be saved, anything else has to be playable sooner or later
+// for it to be a play clue. A clue that can't be either is kept hold of.
+fn classify(
+    game_state: &GameState,
+    giver: usize,
+    receiver: usize,
+    hint: Hint,
+    focus: Focus,
+) -> ClueKind {
+    let (colors, numbers) = clued_identity(game_state, receiver, hint, focus);
+    let (previously_touched, on_chop) = if receiver == 0 {
+        let hand = &game_state.player_hand;
+        let on_chop = hand.get_chop().map(|(id, _)| id) == Some(focus.id());
+        (hand[focus.index()].3, on_chop)
+    } else {
+        let hand = &game_state.team_hands[receiver - 1];
+        let on_chop = hand.get_chop().map(|(id, _, _, _, _)| id) == Some(focus.id());
+        (hand.hand[focus.index()].3, on_chop)
+    };
+    if previously_touched {
+        return if could_be_playable(game_state, colors, numbers) {
+            ClueKind::Tempo
+        } else {
+            ClueKind::Fix
+        };
+    }
+    let known = !colors.is_empty() && !numbers.is_empty();
+    if known && game_state.is_playable(colors, numbers) {
+        return ClueKind::Play;
+    }
+
+    let critical = game_state.is_critical(colors, numbers);
+    match hint {
+        Hint::Number(5) if on_chop => ClueKind::FiveSave,
+        Hint::Number(2) if on_chop => ClueKind::TwoSave,
+        _ if on_chop && critical => ClueKind::CriticalSave,
+        _ if could_be_playable(game_state, colors, numbers)
+            || bluff(game_state, giver, receiver, hint, focus).is_some()
+            || connections_to_focus(game_state, giver, receiver, hint, focus).is_some() =>
+        {
+            ClueKind::Play
+        }
+        Hint::Number(5) => ClueKind::Stall,
+        // Not in danger and not going to be played, so it's only being kept safe
+        _ if critical => ClueKind::CriticalSave,
+        _ => ClueKind::Stall,
+    }
+}
+
+// Whether `player` (as in `Action::Hint`) would read `hint` from us as anything
+// but a play clue on a card they can't play
+pub(crate) fn safe_to_give(game_state: &GameState, player: usize, hint: Hint) -> bool {
+    let Ok(focus) = game_state.get_focus_for_hint(player, hint) else {
+        return false;
+    };
+    match classify(game_state, 0, player + 1, hint, focus) {
+        ClueKind::Play | ClueKind::Tempo => {
+            let (_, color, number, _) = game_state.team_hands[player].hand[focus.index()];
+            game_state.is_playable(color.into(), number.into())
+        }
+        _ => true,
+    }
+}
+
+// Gen 3: what we should be thinking about our focused card
+fn respond(kind: ClueKind, hint: Hint, card: CardId, turn: usize) -> Option<ThoughtType> {
+    match kind {
+        ClueKind::Play | ClueKind::Tempo => Some(ThoughtType::Play(PlayThought::new(card, turn))),
+        ClueKind::FiveSave => Some(ThoughtType::FiveSave(FiveSaveThought::new(card))),
+        ClueKind::TwoSave => Some(ThoughtType::TwoSave(TwoSaveThought::new(card))),
+        ClueKind::Stall if hint == Hint::Number(5) => {
+            Some(ThoughtType::FiveStall(FiveStallThought::new(card)))
+        }
+        ClueKind::CriticalSave | ClueKind::Stall => Some(ThoughtType::Save(SaveThought::new(card))),
+        // Our knowledge of the card has already been corrected
+        ClueKind::Fix => None,
+    }
+}
+
+fn could_be_playable(game_state: &GameState, colors: ColorFlags, numbers: NumberFlags) -> bool {
+    colors
+        .iter()
+        .any(|c| numbers.iter().any(|n| game_state.is_playable(c, n)))
+}
+
+// Copies of a card that aren't in a teammate's hand, played or discarded
+fn unseen_copies(game_state: &GameState, color: Color, number: usize) -> usize {
+    let seen = game_state
+        .team_hands
+        .iter()
+        .flat_map(|h| h.hand.iter().map(|(_, c, n, _)| (*c, *n)))
+        .chain(
+            game_state
+                .played
+                .get(&color)
+                .into_iter()
+                .flatten()
+                .map(|(_, n)| (color, *n)),
+        )
+        .chain(game_state.discarded.iter().map(|(_, c, n)| (*c, *n)))
+        .filter(|card| *card == (color, number))
+        .count();
+    copies(number).saturating_sub(seen)
+}
@@ -0,0 +1,66 @@
+use crate::{rules::MAX_HINTS, CardId, Color, GameState, Hint, IllegalAction};
+
+// Something that happened at the table, with seats relative to us like in `GameState`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameEvent {
+    // We only get to see what was drawn if it wasn't us who drew it
+    Draw(usize, CardId, Option<(Color, usize)>),
+    Play(usize, CardId, Color, usize),
+    Discard(usize, CardId, Color, usize),
+    Bomb(usize, CardId, Color, usize),
+    // Giver, receiver, the hint and the cards it touched
+    Hint(usize, usize, Hint, Vec<CardId>),
+    Turn(usize, usize),
+}
+
+impl GameState {
+    // Keeps our view of the game up to date with what's being reported to us,
+    // rather than working out what happens ourselves like `apply`
+    pub fn record(&mut self, event: &GameEvent) -> Result<(), IllegalAction> {
+        match event {
+            GameEvent::Draw(seat, id, identity) => {
+                self.draw_into_hand(*seat, *id, *identity)?;
+                if self.deck.last() == Some(id) {
+                    self.deck.pop();
+                } else {
+                    self.deck.retain(|cid| cid != id);
+                }
+            }
+            GameEvent::Play(seat, id, color, number) => {
+                self.remove_from_hand(*seat, *id)?;
+                self.played.entry(*color).or_default().push((*id, *number));
+                if *number == 5 && self.hint_count < MAX_HINTS {
+                    self.hint_count += 1;
+                }
+            }
+            GameEvent::Discard(seat, id, color, number) => {
+                self.remove_from_hand(*seat, *id)?;
+                self.discarded.push((*id, *color, *number));
+                self.hint_count = (self.hint_count + 1).min(MAX_HINTS);
+            }
+            GameEvent::Bomb(seat, id, color, number) => {
+                self.remove_from_hand(*seat, *id)?;
+                self.discarded.push((*id, *color, *number));
+                self.strikes += 1;
+            }
+            GameEvent::Hint(_, receiver, hint, touched) => {
+                if *receiver >= self.players() {
+                    return Err(IllegalAction::NoSuchPlayer(*receiver));
+                }
+                self.receive_hint(*receiver, *hint, touched);
+                self.hint_count = self.hint_count.saturating_sub(1);
+            }
+            GameEvent::Turn(turn, seat) => {
+                self.turns_left = match self.turns_left {
+                    Some(turns) => Some(turns.saturating_sub(1)),
+                    None if self.deck.is_empty() => Some(self.players()),
+                    None => None,
+                };
+                self.turn_counter = *turn;
+                self.current_player = *seat;
+            }
+        }
+
+        Ok(())
+    }
+}
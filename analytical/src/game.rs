@@ -0,0 +1,190 @@
+use crate::{
+    rules::{copies, hand_size, MAX_PLAYERS, MIN_PLAYERS},
+    Action, BuildError, CardId, Color, ColorFlags, GameState, GameStatus, IllegalAction,
+    NumberFlags, Outcome, PlayerHand, TeammateHand,
+};
+
+// The whole game, with nothing hidden.
+//
+// Players should only ever be given a `view` of it, which hides their own cards
+// and the deck from them.
+pub struct Game {
+    players: usize,
+    // Seen from seat 0, with their hand and the deck in `hidden`
+    truth: GameState,
+    // Every card in the order it's drawn
+    deck: Vec<(CardId, Color, usize)>,
+}
+
+impl Game {
+    // A standard deck, shuffled from `seed`
+    pub fn new(players: usize, seed: u64) -> Result<Self, BuildError> {
+        let mut deck: Vec<_> = Color::ALL
+            .iter()
+            .flat_map(|c| (1..=5).flat_map(move |n| std::iter::repeat((*c, n)).take(copies(n))))
+            .collect();
+        Rng(seed).shuffle(&mut deck);
+
+        Self::from_deck(players, deck)
+    }
+
+    // Deals from `deck`, with the first card being drawn first
+    pub fn from_deck(players: usize, deck: Vec<(Color, usize)>) -> Result<Self, BuildError> {
+        if !(MIN_PLAYERS..=MAX_PLAYERS).contains(&players) {
+            return Err(BuildError::WrongPlayerCount(players));
+        }
+        // Cards are numbered in the order they're drawn, like on hanab.live
+        let deck: Vec<_> = deck
+            .into_iter()
+            .enumerate()
+            .map(|(i, (c, n))| (CardId(i), c, n))
+            .collect();
+
+        // Everyone is dealt their hand in turn, drawing into their leftmost slot
+        let dealt = (hand_size(players) * players).min(deck.len());
+        let mut hands: Vec<Vec<_>> = deck[..dealt]
+            .chunks(hand_size(players))
+            .map(|h| h.iter().rev().copied().collect())
+            .collect();
+        hands.resize(players, Vec::new());
+
+        let mut builder = GameState::builder()
+            .player_hand(PlayerHand::new(
+                hands[0]
+                    .iter()
+                    .map(|(id, _, _)| (*id, ColorFlags::all(), NumberFlags::all(), false))
+                    .collect(),
+            ))
+            .deck(deck[dealt..].iter().rev().map(|(id, _, _)| *id).collect());
+        for (id, color, number) in hands[0].iter().chain(deck[dealt..].iter()) {
+            builder = builder.hidden(*id, *color, *number);
+        }
+        for hand in &hands[1..] {
+            builder = builder.teammate_hand(TeammateHand::new(
+                hand.iter().map(|(id, c, n)| (*id, *c, *n, false)).collect(),
+            ));
+        }
+
+        Ok(Self {
+            players,
+            truth: builder.build()?,
+            deck,
+        })
+    }
+
+    pub fn players(&self) -> usize {
+        self.players
+    }
+
+    pub fn deck(&self) -> &[(CardId, Color, usize)] {
+        &self.deck
+    }
+
+    pub fn current_player(&self) -> usize {
+        self.truth.current_player
+    }
+
+    pub fn hint_count(&self) -> usize {
+        self.truth.hint_count
+    }
+
+    pub fn status(&self) -> GameStatus {
+        self.truth.status()
+    }
+
+    pub fn turn(&self) -> usize {
+        self.truth.turn_counter
+    }
+
+    // The card that will be drawn next, if there are any left
+    pub fn next_card(&self) -> Option<(CardId, Color, usize)> {
+        let id = self.truth.deck.last()?;
+        self.deck.iter().find(|(cid, _, _)| cid == id).copied()
+    }
+
+    pub fn touched_cards(&self) -> Vec<CardId> {
+        self.truth
+            .player_hand
+            .iter()
+            .filter(|(_, _, _, touched)| *touched)
+            .map(|(id, _, _, _)| *id)
+            .chain(self.truth.team_hands.iter().flat_map(|h| {
+                h.hand
+                    .iter()
+                    .filter(|(_, _, _, touched)| *touched)
+                    .map(|(id, _, _, _)| *id)
+            }))
+            .collect()
+    }
+
+    // Applies the current player's action, from their point of view
+    pub fn apply(&mut self, action: Action) -> Result<Outcome, IllegalAction> {
+        self.truth.apply(action)
+    }
+
+    // What the player in `seat` gets to see of the game: everyone else's hands
+    // starting from the player after them, and only what they've been told about their own
+    pub fn view(&self, seat: usize) -> GameState {
+        let truth = &self.truth;
+        let player_hand = if seat == 0 {
+            truth.player_hand.clone()
+        } else {
+            PlayerHand(truth.team_hands[seat - 1].empathy.clone())
+        };
+        let team_hands = (1..self.players)
+            .map(|offset| (seat + offset) % self.players)
+            .map(|other| {
+                if other == 0 {
+                    TeammateHand {
+                        hand: truth
+                            .player_hand
+                            .iter()
+                            .map(|(id, _, _, touched)| {
+                                let (color, number) = truth.hidden[id];
+                                (*id, color, number, *touched)
+                            })
+                            .collect(),
+                        empathy: truth.player_hand.0.clone(),
+                    }
+                } else {
+                    truth.team_hands[other - 1].clone()
+                }
+            })
+            .collect();
+
+        GameState {
+            team_hands,
+            player_hand,
+            discarded: truth.discarded.clone(),
+            played: truth.played.clone(),
+            hint_count: truth.hint_count,
+            strikes: truth.strikes,
+            turn_counter: truth.turn_counter,
+            current_player: (truth.current_player + self.players - seat) % self.players,
+            deck: truth.deck.clone(),
+            // Nobody gets to see their own cards, or what's coming
+            hidden: Default::default(),
+            turns_left: truth.turns_left,
+        }
+    }
+}
+
+// SplitMix64, so games can be replayed from their seed
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = (self.next() % (i as u64 + 1)) as usize;
+            items.swap(i, j);
+        }
+    }
+}
@@ -1,56 +1,101 @@
-use crate::{Action, CardId, CardType, GameState, Hint, Poll, Thought};
+use std::cmp::Reverse;
+
+use crate::{
+    rules::{copies, MAX_STRIKES},
+    Action, CardId, CardType, Color, ColorFlags, GameState, Hint, NumberFlags, Poll, Reasoning,
+    Thought,
+};
 
 pub struct PromptThought {
     prompted: Vec<CardId>,
     hinted: CardId,
 }
 
+impl PromptThought {
+    pub fn new(prompted: Vec<CardId>, hinted: CardId) -> Self {
+        Self { prompted, hinted }
+    }
+}
+
 impl Thought for PromptThought {
-    fn poll<const P: usize, const H: usize>(&mut self, game_state: &GameState<P, H>) -> Poll {
+    fn card(&self) -> Option<CardId> {
+        Some(self.hinted)
+    }
+
+    fn poll(&mut self, game_state: &GameState, reasoning: &mut Reasoning) -> Poll {
         let finessed: Vec<_> = self
             .prompted
             .iter()
             .map(|c| c.resolve(game_state))
             .collect();
-        let _hinted = self.hinted.resolve(game_state);
+        let hinted = self.hinted.resolve(game_state);
 
-        log::info!("I think a prompt is happening...");
-        print!("Have all the prompted cards been played? ");
+        reasoning.note("I think a prompt is happening...");
+        reasoning.question("Have all the prompted cards been played?");
         if finessed
             .iter()
             .all(|c| matches!(c.typ, CardType::Played(_, _)))
         {
-            log::info!("Yes!");
-            log::info!("Therefore I should complete the line!");
+            reasoning.answer("Yes!");
+            if !matches!(hinted.typ, CardType::PlayerHand(_)) {
+                reasoning
+                    .note("And it's not my card that finishes it, so there's nothing left to do");
+                return Poll::Finished(None);
+            }
+            reasoning.note("Therefore I should complete the line!");
             return Poll::Finished(Some(Action::Play(self.hinted)));
         }
-        log::info!("No!");
-        log::info!("Were any of the prompted card dicarded? ");
+        reasoning.answer("No!");
+        reasoning.question("Were any of the prompted card dicarded?");
         if finessed
             .iter()
             .any(|c| matches!(c.typ, CardType::Discarded(_, _)))
         {
-            log::info!("Yes!");
-            log::info!("Therefore the line is broken, and I should stop thinking about this...");
+            reasoning.answer("Yes!");
+            reasoning
+                .note("Therefore the line is broken, and I should stop thinking about this...");
             return Poll::Finished(None);
         }
 
-        log::info!("No!");
-        log::info!("The line is still playing out, and I should wait for it");
+        reasoning.answer("No!");
+        reasoning.note("The line is still playing out, and I should wait for it");
         Poll::Pending(None)
     }
 }
 
 pub struct PromptedThought {
     prompted: CardId,
+    // The cards that have to be played before ours, whoever's holding them
+    earlier: Vec<CardId>,
     hinted: CardId,
 }
 
+impl PromptedThought {
+    pub fn new(prompted: CardId, earlier: Vec<CardId>, hinted: CardId) -> Self {
+        Self {
+            prompted,
+            earlier,
+            hinted,
+        }
+    }
+}
+
 impl Thought for PromptedThought {
-    fn poll<const P: usize, const H: usize>(&mut self, game_state: &GameState<P, H>) -> Poll {
+    fn card(&self) -> Option<CardId> {
+        Some(self.prompted)
+    }
+
+    fn poll(&mut self, game_state: &GameState, reasoning: &mut Reasoning) -> Poll {
         let card = self.prompted.resolve(game_state);
-        log::info!("I think I am being prompted with a {}...", card);
-        log::info!("I should play that card!");
+        if !matches!(card.typ, CardType::PlayerHand(_)) {
+            reasoning.note("The card I thought was prompted isn't in my hand any more...");
+            return Poll::Finished(None);
+        }
+        reasoning.note(format!("I think I am being prompted with a {}...", card));
+        if let Some(poll) = wait_for(&self.earlier, game_state, reasoning) {
+            return poll;
+        }
+        reasoning.note("I should play that card!");
         Poll::Finished(Some(Action::Play(self.prompted)))
     }
 }
@@ -60,69 +105,223 @@ pub struct FinesseThought {
     hinted: CardId,
 }
 
+impl FinesseThought {
+    pub fn new(finessed: Vec<CardId>, hinted: CardId) -> Self {
+        Self { finessed, hinted }
+    }
+}
+
 impl Thought for FinesseThought {
-    fn poll<const P: usize, const H: usize>(&mut self, game_state: &GameState<P, H>) -> Poll {
+    fn card(&self) -> Option<CardId> {
+        Some(self.hinted)
+    }
+
+    fn poll(&mut self, game_state: &GameState, reasoning: &mut Reasoning) -> Poll {
         let finessed: Vec<_> = self
             .finessed
             .iter()
             .map(|c| c.resolve(game_state))
             .collect();
-        let _hinted = self.hinted.resolve(game_state);
+        let hinted = self.hinted.resolve(game_state);
 
-        log::info!("I think a finesse is happening...");
-        print!("Have all the finessed cards been played? ");
+        reasoning.note("I think a finesse is happening...");
+        reasoning.question("Have all the finessed cards been played?");
         if finessed
             .iter()
             .all(|c| matches!(c.typ, CardType::Played(_, _)))
         {
-            log::info!("Yes!");
-            log::info!("Therefore I should complete the line!");
+            reasoning.answer("Yes!");
+            if !matches!(hinted.typ, CardType::PlayerHand(_)) {
+                reasoning
+                    .note("And it's not my card that finishes it, so there's nothing left to do");
+                return Poll::Finished(None);
+            }
+            reasoning.note("Therefore I should complete the line!");
             return Poll::Finished(Some(Action::Play(self.hinted)));
         }
-        log::info!("No!");
-        log::info!("Were any of the finessed card dicarded? ");
+        reasoning.answer("No!");
+        reasoning.question("Were any of the finessed card dicarded?");
         if finessed
             .iter()
             .any(|c| matches!(c.typ, CardType::Discarded(_, _)))
         {
-            log::info!("Yes!");
-            log::info!("Therefore the line is broken, and I should stop thinking about this...");
+            reasoning.answer("Yes!");
+            reasoning
+                .note("Therefore the line is broken, and I should stop thinking about this...");
             return Poll::Finished(None);
         }
 
-        log::info!("No!");
-        log::info!("The line is still playing out, and I should wait for it");
+        reasoning.answer("No!");
+        reasoning.note("The line is still playing out, and I should wait for it");
         Poll::Pending(None)
     }
 }
 
 pub struct FinessedThought {
     card: CardId,
+    // The cards that have to be played before ours, whoever's holding them
+    earlier: Vec<CardId>,
+}
+
+impl FinessedThought {
+    pub fn new(card: CardId, earlier: Vec<CardId>) -> Self {
+        Self { card, earlier }
+    }
 }
 
 impl Thought for FinessedThought {
-    fn poll<const P: usize, const H: usize>(&mut self, game_state: &GameState<P, H>) -> Poll {
+    fn card(&self) -> Option<CardId> {
+        Some(self.card)
+    }
+
+    fn poll(&mut self, game_state: &GameState, reasoning: &mut Reasoning) -> Poll {
         let card = self.card.resolve(game_state);
-        log::info!("I think I am being finessed with a {}...", card);
-        log::info!("I should play that card!");
+        if !matches!(card.typ, CardType::PlayerHand(_)) {
+            reasoning.note("The card I thought was finessed isn't in my hand any more...");
+            return Poll::Finished(None);
+        }
+        reasoning.note(format!("I think I am being finessed with a {}...", card));
+        if let Some(poll) = wait_for(&self.earlier, game_state, reasoning) {
+            return poll;
+        }
+        reasoning.note("I should play that card!");
         Poll::Finished(Some(Action::Play(self.card)))
     }
 }
 
+// Our card can't be played until every card before it in the line has been,
+// and if one of them was lost the line is broken
+fn wait_for(earlier: &[CardId], game_state: &GameState, reasoning: &mut Reasoning) -> Option<Poll> {
+    if earlier.is_empty() {
+        return None;
+    }
+    let earlier: Vec<_> = earlier.iter().map(|c| c.resolve(game_state)).collect();
+    reasoning.question("Have the cards before mine been played?");
+    if earlier
+        .iter()
+        .any(|c| matches!(c.typ, CardType::Discarded(_, _)))
+    {
+        reasoning.answer("One of them was lost, so the line is broken...");
+        return Some(Poll::Finished(None));
+    }
+    if !earlier
+        .iter()
+        .all(|c| matches!(c.typ, CardType::Played(_, _)))
+    {
+        reasoning.answer("Not yet, I should wait for them");
+        return Some(Poll::Pending(None));
+    }
+
+    reasoning.answer("Yes!");
+    None
+}
+
+// Someone else has been bluffed: they'll blind play whatever's in their finesse
+// slot, and unless it turns out to connect, the clued card is one-away
+pub struct BluffThought {
+    bluffed: CardId,
+    hinted: CardId,
+}
+
+impl BluffThought {
+    pub fn new(bluffed: CardId, hinted: CardId) -> Self {
+        Self { bluffed, hinted }
+    }
+}
+
+impl Thought for BluffThought {
+    fn card(&self) -> Option<CardId> {
+        Some(self.hinted)
+    }
+
+    fn poll(&mut self, game_state: &GameState, reasoning: &mut Reasoning) -> Poll {
+        let bluffed = self.bluffed.resolve(game_state);
+        let hinted = self.hinted.resolve(game_state);
+
+        reasoning.note("I think a bluff is happening...");
+        reasoning.question("Has the bluffed card been played?");
+        let (color, number) = match bluffed.typ {
+            CardType::Played(color, number) => (color, number),
+            CardType::Discarded(_, _) => {
+                reasoning.note("It was lost, so I should stop thinking about this...");
+                return Poll::Finished(None);
+            }
+            _ => {
+                reasoning.answer("No! I should wait for it");
+                return Poll::Pending(None);
+            }
+        };
+
+        reasoning.answer(format!("Yes! It was a {}", bluffed));
+        let CardType::PlayerHand(card) = hinted.typ else {
+            reasoning.note("And it's not my card that was clued, so there's nothing left to do");
+            return Poll::Finished(None);
+        };
+        if card.color.contains(color.into()) && card.number.contains((number + 1).into()) {
+            reasoning.note("My card could follow on from it, so it was a finesse after all!");
+            return Poll::Finished(Some(Action::Play(self.hinted)));
+        }
+
+        reasoning.note("It doesn't connect to my card, so mine must be one-away");
+        Poll::Finished(None)
+    }
+}
+
+// We're the one being bluffed, so we play our finesse slot and that's the end of it
+pub struct BluffedThought {
+    finessed: FinessedThought,
+}
+
+impl BluffedThought {
+    pub fn new(card: CardId) -> Self {
+        Self {
+            finessed: FinessedThought::new(card, Vec::new()),
+        }
+    }
+}
+
+impl Thought for BluffedThought {
+    fn card(&self) -> Option<CardId> {
+        self.finessed.card()
+    }
+
+    fn poll(&mut self, game_state: &GameState, reasoning: &mut Reasoning) -> Poll {
+        reasoning.note("I think I might be being bluffed...");
+        self.finessed.poll(game_state, reasoning)
+    }
+}
+
 pub struct PlayThought {
     card: CardId,
     turn: usize,
 }
 
+impl PlayThought {
+    pub fn new(card: CardId, turn: usize) -> Self {
+        Self { card, turn }
+    }
+}
+
 impl Thought for PlayThought {
-    fn poll<const P: usize, const H: usize>(&mut self, game_state: &GameState<P, H>) -> Poll {
-        let _card = self.card.resolve(game_state);
-        log::info!(
+    fn card(&self) -> Option<CardId> {
+        Some(self.card)
+    }
+
+    fn poll(&mut self, game_state: &GameState, reasoning: &mut Reasoning) -> Poll {
+        let card = self.card.resolve(game_state);
+        reasoning.note(format!(
             "I was clued that this card was playable on turn {} ({} turns ago)...",
             self.turn,
             game_state.turn_counter - self.turn
-        );
-        log::info!("I will trust that is is playable!");
+        ));
+        if game_state.strikes + 1 >= MAX_STRIKES && !card.is_playable(game_state) {
+            reasoning.note(
+                "But one more bomb would lose the game, and I can't be sure it's playable...",
+            );
+            reasoning.note("I'll hold onto it for now");
+            return Poll::Pending(None);
+        }
+        reasoning.note("I will trust that is is playable!");
         Poll::Finished(Some(Action::Play(self.card)))
     }
 }
@@ -131,22 +330,29 @@ pub struct FiveSaveThought {
     card: CardId,
 }
 
+impl FiveSaveThought {
+    pub fn new(card: CardId) -> Self {
+        Self { card }
+    }
+}
+
 impl Thought for FiveSaveThought {
-    fn poll<const P: usize, const H: usize>(
-        &mut self,
-        game_state: &GameState<P, H>,
-    ) -> crate::Poll {
+    fn card(&self) -> Option<CardId> {
+        Some(self.card)
+    }
+
+    fn poll(&mut self, game_state: &GameState, reasoning: &mut Reasoning) -> crate::Poll {
         let card = self.card.resolve(game_state);
-        log::info!(
+        reasoning.question(format!(
             "I'm wondering if my five card is playable? I think it's a: {}",
             card
-        );
+        ));
         if card.is_playable(game_state) {
-            log::info!("It is! I should play that then");
+            reasoning.answer("It is! I should play that then");
             return Poll::Finished(Some(Action::Play(self.card)));
         }
 
-        log::info!("It's not...I should wait then");
+        reasoning.answer("It's not...I should wait then");
         Poll::Pending(None)
     }
 }
@@ -155,22 +361,29 @@ pub struct TwoSaveThought {
     card: CardId,
 }
 
+impl TwoSaveThought {
+    pub fn new(card: CardId) -> Self {
+        Self { card }
+    }
+}
+
 impl Thought for TwoSaveThought {
-    fn poll<const P: usize, const H: usize>(
-        &mut self,
-        game_state: &GameState<P, H>,
-    ) -> crate::Poll {
+    fn card(&self) -> Option<CardId> {
+        Some(self.card)
+    }
+
+    fn poll(&mut self, game_state: &GameState, reasoning: &mut Reasoning) -> crate::Poll {
         let card = self.card.resolve(game_state);
-        log::info!(
+        reasoning.question(format!(
             "I'm wondering if my two card is playable? I think it's a: {}",
             card
-        );
+        ));
         if card.is_playable(game_state) {
-            log::info!("It is! I should play that then");
+            reasoning.answer("It is! I should play that then");
             return Poll::Finished(Some(Action::Play(self.card)));
         }
 
-        log::info!("It's not...I should wait then");
+        reasoning.answer("It's not...I should wait then");
         Poll::Pending(None)
     }
 }
@@ -179,22 +392,29 @@ pub struct SaveThought {
     card: CardId,
 }
 
+impl SaveThought {
+    pub fn new(card: CardId) -> Self {
+        Self { card }
+    }
+}
+
 impl Thought for SaveThought {
-    fn poll<const P: usize, const H: usize>(
-        &mut self,
-        game_state: &GameState<P, H>,
-    ) -> crate::Poll {
+    fn card(&self) -> Option<CardId> {
+        Some(self.card)
+    }
+
+    fn poll(&mut self, game_state: &GameState, reasoning: &mut Reasoning) -> crate::Poll {
         let card = self.card.resolve(game_state);
-        log::info!(
+        reasoning.question(format!(
             "I'm wondering if my saved card is playable? I think it's a: {}",
             card
-        );
+        ));
         if card.is_playable(game_state) {
-            log::info!("It is! I should play that then");
+            reasoning.answer("It is! I should play that then");
             return Poll::Finished(Some(Action::Play(self.card)));
         }
 
-        log::info!("It's not...I should wait then");
+        reasoning.answer("It's not...I should wait then");
         Poll::Pending(None)
     }
 }
@@ -203,22 +423,29 @@ pub struct FiveStallThought {
     card: CardId,
 }
 
+impl FiveStallThought {
+    pub fn new(card: CardId) -> Self {
+        Self { card }
+    }
+}
+
 impl Thought for FiveStallThought {
-    fn poll<const P: usize, const H: usize>(
-        &mut self,
-        game_state: &GameState<P, H>,
-    ) -> crate::Poll {
+    fn card(&self) -> Option<CardId> {
+        Some(self.card)
+    }
+
+    fn poll(&mut self, game_state: &GameState, reasoning: &mut Reasoning) -> crate::Poll {
         let card = self.card.resolve(game_state);
-        log::info!(
+        reasoning.question(format!(
             "I'm wondering if my stalled 5 card is playable? I think it's a: {}",
             card
-        );
+        ));
         if card.is_playable(game_state) {
-            log::info!("It is! I should play that then");
+            reasoning.answer("It is! I should play that then");
             return Poll::Finished(Some(Action::Play(self.card)));
         }
 
-        log::info!("It's not...I should wait then");
+        reasoning.answer("It's not...I should wait then");
         Poll::Pending(None)
     }
 }
@@ -226,44 +453,49 @@ impl Thought for FiveStallThought {
 pub struct EarlyGameThought {}
 
 impl Thought for EarlyGameThought {
-    fn poll<const P: usize, const H: usize>(&mut self, game_state: &GameState<P, H>) -> Poll {
+    fn poll(&mut self, game_state: &GameState, reasoning: &mut Reasoning) -> Poll {
         if game_state.hint_count == 0 {
             return Poll::Pending(None);
         }
 
         let playable_cards = game_state.playable_cards_in_teammate_hands();
         for (id, card) in playable_cards {
-            log::info!(
+            reasoning.note(format!(
                 "I've noticed that one of player {}'s carss is playable!",
                 card.player
-            );
+            ));
             if card.touched {
-                log::info!("However, it's already been touched, so they probably know about it...");
+                reasoning
+                    .note("However, it's already been touched, so they probably know about it...");
+                continue;
+            }
+            if game_state.teammate_knows_playable(card.player, card.index) {
+                reasoning.note("However, they can already tell it's playable...");
+                continue;
+            }
+            if id.resolve(game_state).is_trash(game_state) {
+                reasoning.note("However, someone else already has it touched...");
                 continue;
             }
 
             // We'll hint that then, as long it would be the focus
             let mut hint = Hint::Number(card.number);
-            let mut focus_id = match game_state.get_focus_for_hint(card.player, hint) {
-                crate::Focus::Chop(id, _) => id,
-                crate::Focus::NewCard(id, _) => id,
-                crate::Focus::LeftMost(id, _) => id,
-            };
-            if focus_id != id {
+            let mut focus_id = game_state
+                .get_focus_for_hint(card.player, hint)
+                .map(|f| f.id());
+            if focus_id != Ok(id) {
                 // What about with a color hint?
                 hint = Hint::Color(card.color);
-                focus_id = match game_state.get_focus_for_hint(card.player, hint) {
-                    crate::Focus::Chop(id, _) => id,
-                    crate::Focus::NewCard(id, _) => id,
-                    crate::Focus::LeftMost(id, _) => id,
-                };
-                if focus_id != id {
-                    log::info!("I can't hint while focusing that card, so I can't hint it...");
+                focus_id = game_state
+                    .get_focus_for_hint(card.player, hint)
+                    .map(|f| f.id());
+                if focus_id != Ok(id) {
+                    reasoning.note("I can't hint while focusing that card, so I can't hint it...");
                     continue;
                 }
             }
 
-            log::info!("And it's not been touched, let's hint it!");
+            reasoning.note("And it's not been touched, let's hint it!");
             return Poll::Pending(Some(Action::Hint(card.player, hint)));
         }
 
@@ -271,32 +503,38 @@ impl Thought for EarlyGameThought {
         // Is there a critical card instead?
         let critical_cards = game_state.critical_cards_in_teammate_hands();
         for (id, card) in critical_cards {
-            log::info!(
+            reasoning.note(format!(
                 "I've noticed that one of player {}'s cards is critical!",
                 card.player
-            );
+            ));
             if card.touched {
-                log::info!("However, it's already been touched, so they probably know about it...");
+                reasoning
+                    .note("However, it's already been touched, so they probably know about it...");
+                continue;
+            }
+            if id.resolve(game_state).is_trash(game_state) {
+                reasoning.note("However, it can never be played anyway...");
+                continue;
+            }
+            // Anywhere else it's safe, and a clue would be read as a play clue
+            if game_state.get_chop_for_teammate(card.player).map(|c| c.id) != Some(id) {
+                reasoning.note("However, it's not on their chop, so it's safe for now...");
                 continue;
             }
 
             // We'll hint that then, as long it would be the focus
             let mut hint = Hint::Number(card.number);
-            let mut focus_id = match game_state.get_focus_for_hint(card.player, hint) {
-                crate::Focus::Chop(id, _) => id,
-                crate::Focus::NewCard(id, _) => id,
-                crate::Focus::LeftMost(id, _) => id,
-            };
-            if focus_id != id {
+            let mut focus_id = game_state
+                .get_focus_for_hint(card.player, hint)
+                .map(|f| f.id());
+            if focus_id != Ok(id) {
                 // What about with a color hint?
                 hint = Hint::Color(card.color);
-                focus_id = match game_state.get_focus_for_hint(card.player, hint) {
-                    crate::Focus::Chop(id, _) => id,
-                    crate::Focus::NewCard(id, _) => id,
-                    crate::Focus::LeftMost(id, _) => id,
-                };
-                if focus_id != id {
-                    log::info!("I can't hint while focusing that card, so I can't hint it...");
+                focus_id = game_state
+                    .get_focus_for_hint(card.player, hint)
+                    .map(|f| f.id());
+                if focus_id != Ok(id) {
+                    reasoning.note("I can't hint while focusing that card, so I can't hint it...");
                     // TODO: With a better playing algo, we should check if this player has a playable card instead
                     // Because of the above check for playable cards, the only instance we'll miss a card is if it's both playable
                     // and critical and we can't properly hint it.
@@ -306,7 +544,7 @@ impl Thought for EarlyGameThought {
                 }
             }
 
-            log::info!("And it's not been touched, let's hint it!");
+            reasoning.note("And it's not been touched, let's hint it!");
             return Poll::Pending(Some(Action::Hint(card.player, hint)));
         }
 
@@ -323,29 +561,20 @@ impl Thought for EarlyGameThought {
                 })
             })
             .filter(|(_, _, _, n, _, _)| *n == 2)
-            .filter(|(_, _, color, _, _, _)| {
-                game_state
-                    .played
-                    .get(color)
-                    .unwrap_or(&Vec::new())
-                    .iter()
-                    .any(|(_, n)| *n == 2)
-            })
+            .filter(|(_, id, _, _, _, _)| !id.resolve(game_state).is_trash(game_state))
             .filter(|(_, _, _, _, touched, _)| !touched);
 
         for card in twos_on_chops {
-            log::info!(
+            reasoning.note(format!(
                 "Player {} has a 2 on the chop, I should probably try and save it...",
                 card.0
-            );
+            ));
 
-            let focus_id = match game_state.get_focus_for_hint(card.0, Hint::Number(2)) {
-                crate::Focus::Chop(id, _) => id,
-                crate::Focus::NewCard(id, _) => id,
-                crate::Focus::LeftMost(id, _) => id,
-            };
-            if focus_id != card.1 {
-                log::info!("I can't hint while focusing that card, so I can't hint it...");
+            let focus_id = game_state
+                .get_focus_for_hint(card.0, Hint::Number(2))
+                .map(|f| f.id());
+            if focus_id != Ok(card.1) {
+                reasoning.note("I can't hint while focusing that card, so I can't hint it...");
                 continue;
             }
 
@@ -379,20 +608,101 @@ impl Thought for EarlyGameThought {
             return Poll::Pending(Some(Action::Hint(five_card.0, Hint::Number(5))));
         }
 
-        Poll::Finished(None)
+        // Something worth hinting might turn up later
+        Poll::Pending(None)
     }
 }
 
 pub struct DiscardThought {}
+
+// The card we'd discard, with its slot and whether it's trash or believed
+// critical. Trash goes first, then the chop, then whatever has the most copies
+// left, keeping touched and critical cards for as long as we can.
+pub(crate) fn discard_candidate(game_state: &GameState) -> Option<(usize, CardId, bool, bool)> {
+    let chop = game_state.player_hand.get_chop().map(|(id, _)| id);
+    game_state
+        .player_hand
+        .iter()
+        .enumerate()
+        .map(|(index, (id, color, number, touched))| {
+            let trash = id.resolve(game_state).is_trash(game_state);
+            let critical = !trash && believed_critical(game_state, *color, *number);
+            let rank = (
+                !trash,
+                critical,
+                *touched,
+                Some(*id) != chop,
+                Reverse(copies_left(game_state, *color, *number)),
+                Reverse(index),
+            );
+            (rank, index, *id, trash, critical)
+        })
+        .min_by_key(|(rank, _, _, _, _)| *rank)
+        .map(|(_, index, id, trash, critical)| (index, id, trash, critical))
+}
+
 impl Thought for DiscardThought {
-    fn poll<const P: usize, const H: usize>(&mut self, game_state: &GameState<P, H>) -> Poll {
-        let chop = game_state.player_hand.get_chop();
+    fn poll(&mut self, game_state: &GameState, reasoning: &mut Reasoning) -> Poll {
+        let chop = game_state.player_hand.get_chop().map(|(id, _)| id);
+        let Some((index, id, trash, critical)) = discard_candidate(game_state) else {
+            reasoning.note("I don't have anything left to discard");
+            return Poll::Pending(None);
+        };
+        reasoning.question("Do I know any of my cards are trash?");
+        if trash {
+            reasoning.answer(format!("Yes! Slot {index} can never be played"));
+            return Poll::Pending(Some(Action::Discard(id)));
+        }
+        reasoning.answer("No!");
 
-        // TODO: TBH this should be a lot better, we're only handling it like this
-        // becuase we're discarding as the *last possible* action that could happen.
-        Poll::Pending(Some(Action::Discard(match chop {
-            Some((id, _)) => id,
-            None => game_state.player_hand[0].0,
-        })))
+        if critical {
+            if game_state.hint_count() > 0 {
+                reasoning
+                    .note("Anything I'd discard could be the last copy, I'd rather give a clue");
+                return Poll::Pending(None);
+            }
+            reasoning.note(
+                "Anything I'd discard could be the last copy, but there's nothing else to do",
+            );
+        } else if Some(id) == chop {
+            reasoning.note("I'll discard my chop");
+        } else if chop.is_none() {
+            reasoning.note(format!(
+                "Everything's been touched, so I'll discard slot {index}"
+            ));
+        } else {
+            reasoning.note(format!(
+                "My chop could be critical, so I'll discard slot {index}"
+            ));
+        }
+        Poll::Pending(Some(Action::Discard(id)))
     }
 }
+
+// Every identity a card we can only partly see could have
+pub(crate) fn identities(
+    color: ColorFlags,
+    number: NumberFlags,
+) -> impl Iterator<Item = (Color, usize)> {
+    color.iter().flat_map(move |c| {
+        number
+            .iter()
+            .map(move |n| (Color::try_from(c).unwrap(), usize::try_from(n).unwrap()))
+    })
+}
+
+// Unlike `GameState::is_critical`, this is only true if *every* card it could
+// be is critical, otherwise nothing unknown would ever be safe to discard
+fn believed_critical(game_state: &GameState, color: ColorFlags, number: NumberFlags) -> bool {
+    !color.is_empty()
+        && !number.is_empty()
+        && identities(color, number).all(|(c, n)| game_state.is_critical(c.into(), n.into()))
+}
+
+// The fewest copies there could be left of it, counting the one we're holding
+fn copies_left(game_state: &GameState, color: ColorFlags, number: NumberFlags) -> usize {
+    identities(color, number)
+        .map(|(c, n)| copies(n).saturating_sub(game_state.discarded_count(c, n)))
+        .min()
+        .unwrap_or(0)
+}
@@ -11,73 +11,300 @@ use std::{
     ops::{Deref, Index},
 };
 
+pub use builder::{BuildError, GameStateBuilder};
+pub use clues::{ClueInterpreter, ClueKind, Connection};
+pub use events::GameEvent;
+pub use game::Game;
 use level1::{
-    DiscardThought, EarlyGameThought, FinesseThought, FinessedThought, FiveSaveThought,
-    FiveStallThought, PlayThought, PromptThought, PromptedThought, SaveThought, TwoSaveThought,
+    BluffThought, BluffedThought, DiscardThought, EarlyGameThought, FinesseThought,
+    FinessedThought, FiveSaveThought, FiveStallThought, PlayThought, PromptThought,
+    PromptedThought, SaveThought, TwoSaveThought,
 };
+pub use priorities::Priorities;
 use priority_queue::PriorityQueue;
-
+use rules::copies;
+pub use rules::{GameStatus, IllegalAction, Outcome, MAX_SCORE};
+pub use simulator::{GameRecord, Simulator};
+pub use trace::{DecisionTrace, PolledThought, Reasoning, Step};
+
+mod builder;
+mod clues;
+mod events;
+mod game;
 mod level1;
+mod priorities;
 mod priority_queue;
+#[cfg(all(feature = "serde", feature = "serde_json"))]
+pub mod protocol;
+mod rules;
+mod simulator;
+mod trace;
 
 pub struct Brain {
     thoughts: PriorityQueue<usize, ThoughtType>,
+    generators: Vec<Box<dyn ThoughtGenerator>>,
+    priorities: Priorities,
+    retired: Vec<Retired>,
+    // Kept in step with `thoughts` as thoughts are added and removed
+    index: Thoughts,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum RetireReason {
+    // Played or discarded, so there's nothing left to do with it
+    LeftHand,
+    // Everything it could be has already been played
+    AlreadyPlayed,
+    // It can never be played, or someone else already has it touched
+    Trash,
+}
+
+// A thought that was dropped because the card it was about changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Retired {
+    pub thought: &'static str,
+    pub card: CardId,
+    pub reason: RetireReason,
 }
 
 impl Brain {
     pub fn new() -> Self {
+        Self::with_priorities(Priorities::default())
+    }
+
+    pub fn with_priorities(priorities: Priorities) -> Self {
         let mut queue = PriorityQueue::new();
 
-        queue.push(ThoughtType::EarlyGame(EarlyGameThought {}), 10);
+        let early_game = ThoughtType::EarlyGame(EarlyGameThought {});
+        queue.push(early_game, priorities.early_game);
+
+        Self {
+            thoughts: queue,
+            generators: vec![Box::new(ClueInterpreter::new())],
+            priorities,
+            retired: Vec::new(),
+            index: Thoughts::default(),
+        }
+    }
+
+    pub fn add_generator(&mut self, generator: impl ThoughtGenerator + 'static) {
+        self.generators.push(Box::new(generator));
+    }
+
+    // Called for everything that happens at the table, whoever did it, with
+    // `game_state` being how things were just before it happened
+    pub fn observe(&mut self, event: &GameEvent, game_state: &GameState) {
+        for generator in self.generators.iter_mut() {
+            for thought in generator.observe(event, game_state) {
+                let priority = self.priorities.of(&thought);
+                self.index.add(&thought);
+                self.thoughts.push(thought, priority);
+            }
+        }
+    }
 
-        Self { thoughts: queue }
+    // Thoughts are polled in order until one of them comes up with something we
+    // can actually do. If none of them do, we fall back to the safest legal move.
+    pub fn play(&mut self, game_state: &GameState) -> Result<Action, IllegalAction> {
+        self.play_with_trace(game_state).map(|trace| trace.action)
     }
 
-    pub fn play<const P: usize, const H: usize>(&mut self, game_state: &GameState<P, H>) -> Action {
+    // The same as `play`, along with how we came to the decision
+    pub fn play_with_trace(
+        &mut self,
+        game_state: &GameState,
+    ) -> Result<DecisionTrace, IllegalAction> {
+        if game_state.status() != GameStatus::InProgress {
+            return Err(IllegalAction::GameOver);
+        }
+        self.retire(game_state);
+
+        let mut polled = Vec::new();
         let mut thoughts_to_requeue = Vec::new();
-        let action_to_return;
-        loop {
-            let mut thought = self.thoughts.pop().unwrap();
-            match thought.poll(game_state) {
+        let mut action_to_return = None;
+        while let Some(mut thought) = self.thoughts.pop() {
+            self.index.remove(&thought);
+            let mut reasoning = Reasoning::new();
+            let result = thought.poll(game_state, &mut reasoning);
+            polled.push(PolledThought {
+                thought: thought.name(),
+                card: thought.card(),
+                steps: reasoning.steps().to_vec(),
+                result,
+            });
+            let action = match result {
                 Poll::Pending(action) => {
                     thoughts_to_requeue.push(thought);
-                    if let Some(a) = action {
-                        action_to_return = a;
-                        break;
-                    }
-                }
-                Poll::Finished(Some(action)) => {
-                    action_to_return = action;
-                    break;
+                    action
                 }
-                Poll::Finished(None) => {}
+                Poll::Finished(action) => action,
+            };
+            // A thought that's out of date can come up with something we can't do
+            if let Some(action) = action.filter(|a| game_state.check(*a).is_ok()) {
+                action_to_return = Some(action);
+                break;
             }
         }
         for thought in thoughts_to_requeue.into_iter().rev() {
-            let priority = match thought {
-                ThoughtType::Prompt(_) => 20,
-                ThoughtType::Prompted(_) => 20,
-                ThoughtType::Finesse(_) => 10,
-                ThoughtType::Finessed(_) => 10,
-                ThoughtType::Play(_) => 5,
-                ThoughtType::FiveSave(_) => 6,
-                ThoughtType::TwoSave(_) => 6,
-                ThoughtType::Save(_) => 9,
-                ThoughtType::FiveStall(_) => 1,
-                ThoughtType::EarlyGame(_) => 5,
-                ThoughtType::Discard(_) => 0,
-            };
+            let priority = self.priorities.of(&thought);
+            self.index.add(&thought);
             self.thoughts.push_front(thought, priority);
         }
 
-        action_to_return
+        let fallback = action_to_return.is_none();
+        let action = match action_to_return {
+            Some(action) => action,
+            None => fallback_action(game_state, &mut polled)?,
+        };
+        let trace = DecisionTrace {
+            turn: game_state.turn_counter,
+            retired: self.retired.clone(),
+            polled,
+            fallback,
+            action,
+        };
+        log::debug!("{trace}");
+
+        Ok(trace)
+    }
+}
+
+// Something legal to do when there's nothing left to think about: a discard if
+// we can, otherwise a hint that won't be mistaken for a play clue (a 5 stall if
+// there's one to give). Failing that we discard anyway, even a card that could be
+// critical, and only play our newest card blind as a last resort.
+fn fallback_action(
+    game_state: &GameState,
+    polled: &mut Vec<PolledThought>,
+) -> Result<Action, IllegalAction> {
+    let mut discard_thought = ThoughtType::Discard(DiscardThought {});
+    let mut reasoning = Reasoning::new();
+    let result = discard_thought.poll(game_state, &mut reasoning);
+    polled.push(PolledThought {
+        thought: discard_thought.name(),
+        card: None,
+        steps: reasoning.steps().to_vec(),
+        result,
+    });
+    let discard = match result {
+        Poll::Pending(action) | Poll::Finished(action) => action,
+    };
+    let hints = game_state
+        .team_hands
+        .iter()
+        .enumerate()
+        .flat_map(|(player, hand)| {
+            let fives = hand
+                .hand
+                .iter()
+                .filter(|(_, _, n, t)| *n == 5 && !t)
+                .map(|_| Hint::Number(5));
+            let others = hand
+                .hand
+                .iter()
+                .flat_map(|(_, c, n, _)| [Hint::Number(*n), Hint::Color(*c)]);
+            fives
+                .chain(others)
+                .filter(move |hint| clues::safe_to_give(game_state, player, *hint))
+                .map(move |hint| Action::Hint(player, hint))
+        });
+    let forced_discard =
+        level1::discard_candidate(game_state).map(|(_, id, _, _)| Action::Discard(id));
+    let play = game_state
+        .player_hand
+        .first()
+        .map(|(id, _, _, _)| Action::Play(*id));
+
+    discard
+        .into_iter()
+        .chain(hints)
+        .chain(forced_discard)
+        .chain(play)
+        .find(|action| game_state.check(*action).is_ok())
+        .ok_or(IllegalAction::NoLegalAction)
+}
+
+impl Brain {
+    // Everything we're currently thinking about `card`
+    pub fn thoughts_for(&self, card: CardId) -> &[Belief] {
+        self.index.for_card(card)
+    }
+
+    // The cards we mean to play, oldest first
+    pub fn cards_with_play_intent(&self) -> Vec<CardId> {
+        let mut cards: Vec<_> = self.index.cards_with_intent(Intent::Play).collect();
+        cards.sort();
+        cards
+    }
+
+    // The thoughts dropped at the start of the last turn, and why
+    pub fn retired(&self) -> &[Retired] {
+        &self.retired
+    }
+
+    fn retire(&mut self, game_state: &GameState) {
+        let mut reasons = HashMap::new();
+        let retired = self.thoughts.remove_if(|thought| {
+            let Some(card) = thought.card() else {
+                return false;
+            };
+            let resolved = card.resolve(game_state);
+            let reason = match &resolved.typ {
+                CardType::Played(_, _) | CardType::Discarded(_, _) => RetireReason::LeftHand,
+                CardType::PlayerHand(c) if game_state.all_played(c.color, c.number) => {
+                    RetireReason::AlreadyPlayed
+                }
+                CardType::TeamHand(c) if game_state.all_played(c.color.into(), c.number.into()) => {
+                    RetireReason::AlreadyPlayed
+                }
+                _ if resolved.is_trash(game_state) => RetireReason::Trash,
+                _ => return false,
+            };
+            reasons.insert(card, reason);
+            true
+        });
+        for thought in &retired {
+            self.index.remove(thought);
+        }
+
+        self.retired = retired
+            .iter()
+            .filter_map(|thought| {
+                let card = thought.card()?;
+                Some(Retired {
+                    thought: thought.name(),
+                    card,
+                    reason: reasons[&card],
+                })
+            })
+            .collect();
+    }
+}
+
+impl Default for Brain {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 pub trait Thought {
-    fn poll<const P: usize, const H: usize>(&mut self, game_state: &GameState<P, H>) -> Poll;
+    fn poll(&mut self, game_state: &GameState, reasoning: &mut Reasoning) -> Poll;
+
+    // The card the thought is about, so it can be dropped once that card's gone
+    fn card(&self) -> Option<CardId> {
+        None
+    }
+}
+
+// Comes up with new things to think about from what's happening at the table
+pub trait ThoughtGenerator {
+    fn observe(&mut self, event: &GameEvent, game_state: &GameState) -> Vec<ThoughtType>;
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Poll {
     Pending(Option<Action>),
     Finished(Option<Action>),
@@ -88,6 +315,8 @@ pub enum ThoughtType {
     Prompted(PromptedThought),
     Finesse(FinesseThought),
     Finessed(FinessedThought),
+    Bluff(BluffThought),
+    Bluffed(BluffedThought),
     Play(PlayThought),
     FiveSave(FiveSaveThought),
     TwoSave(TwoSaveThought),
@@ -97,34 +326,112 @@ pub enum ThoughtType {
     Discard(DiscardThought),
 }
 
+impl ThoughtType {
+    // What having this thought says about its card
+    fn belief(&self) -> Option<Belief> {
+        let (intent, number) = match self {
+            ThoughtType::Prompted(_)
+            | ThoughtType::Finessed(_)
+            | ThoughtType::Bluffed(_)
+            | ThoughtType::Play(_) => (Intent::Play, None),
+            ThoughtType::Prompt(_) | ThoughtType::Finesse(_) | ThoughtType::Bluff(_) => {
+                (Intent::Wait, None)
+            }
+            ThoughtType::FiveSave(_) | ThoughtType::FiveStall(_) => (Intent::Save, Some(5)),
+            ThoughtType::TwoSave(_) => (Intent::Save, Some(2)),
+            ThoughtType::Save(_) => (Intent::Save, None),
+            ThoughtType::EarlyGame(_) | ThoughtType::Discard(_) => return None,
+        };
+
+        Some(Belief {
+            thought: self.name(),
+            intent,
+            number,
+        })
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ThoughtType::Prompt(_) => "prompt",
+            ThoughtType::Prompted(_) => "prompted",
+            ThoughtType::Finesse(_) => "finesse",
+            ThoughtType::Finessed(_) => "finessed",
+            ThoughtType::Bluff(_) => "bluff",
+            ThoughtType::Bluffed(_) => "bluffed",
+            ThoughtType::Play(_) => "play",
+            ThoughtType::FiveSave(_) => "five save",
+            ThoughtType::TwoSave(_) => "two save",
+            ThoughtType::Save(_) => "save",
+            ThoughtType::FiveStall(_) => "five stall",
+            ThoughtType::EarlyGame(_) => "early game",
+            ThoughtType::Discard(_) => "discard",
+        }
+    }
+}
+
 impl Thought for ThoughtType {
-    fn poll<const P: usize, const H: usize>(&mut self, game_state: &GameState<P, H>) -> Poll {
+    fn card(&self) -> Option<CardId> {
         match self {
-            ThoughtType::Prompt(t) => t.poll(game_state),
-            ThoughtType::Prompted(t) => t.poll(game_state),
-            ThoughtType::Finesse(t) => t.poll(game_state),
-            ThoughtType::Finessed(t) => t.poll(game_state),
-            ThoughtType::Play(t) => t.poll(game_state),
-            ThoughtType::FiveSave(t) => t.poll(game_state),
-            ThoughtType::TwoSave(t) => t.poll(game_state),
-            ThoughtType::Save(t) => t.poll(game_state),
-            ThoughtType::FiveStall(t) => t.poll(game_state),
-            ThoughtType::EarlyGame(t) => t.poll(game_state),
-            ThoughtType::Discard(t) => t.poll(game_state),
+            ThoughtType::Prompt(t) => t.card(),
+            ThoughtType::Prompted(t) => t.card(),
+            ThoughtType::Finesse(t) => t.card(),
+            ThoughtType::Finessed(t) => t.card(),
+            ThoughtType::Bluff(t) => t.card(),
+            ThoughtType::Bluffed(t) => t.card(),
+            ThoughtType::Play(t) => t.card(),
+            ThoughtType::FiveSave(t) => t.card(),
+            ThoughtType::TwoSave(t) => t.card(),
+            ThoughtType::Save(t) => t.card(),
+            ThoughtType::FiveStall(t) => t.card(),
+            ThoughtType::EarlyGame(t) => t.card(),
+            ThoughtType::Discard(t) => t.card(),
+        }
+    }
+
+    fn poll(&mut self, game_state: &GameState, reasoning: &mut Reasoning) -> Poll {
+        match self {
+            ThoughtType::Prompt(t) => t.poll(game_state, reasoning),
+            ThoughtType::Prompted(t) => t.poll(game_state, reasoning),
+            ThoughtType::Finesse(t) => t.poll(game_state, reasoning),
+            ThoughtType::Finessed(t) => t.poll(game_state, reasoning),
+            ThoughtType::Bluff(t) => t.poll(game_state, reasoning),
+            ThoughtType::Bluffed(t) => t.poll(game_state, reasoning),
+            ThoughtType::Play(t) => t.poll(game_state, reasoning),
+            ThoughtType::FiveSave(t) => t.poll(game_state, reasoning),
+            ThoughtType::TwoSave(t) => t.poll(game_state, reasoning),
+            ThoughtType::Save(t) => t.poll(game_state, reasoning),
+            ThoughtType::FiveStall(t) => t.poll(game_state, reasoning),
+            ThoughtType::EarlyGame(t) => t.poll(game_state, reasoning),
+            ThoughtType::Discard(t) => t.poll(game_state, reasoning),
         }
     }
 }
 
-pub struct GameState<const P: usize, const H: usize> {
-    team_hands: [TeammateHand<H>; P],
-    player_hand: PlayerHand<H>,
+pub struct GameState {
+    team_hands: Vec<TeammateHand>,
+    player_hand: PlayerHand,
     discarded: Vec<(CardId, Color, usize)>,
     played: HashMap<Color, Vec<(CardId, usize)>>,
     hint_count: usize,
+    strikes: usize,
     turn_counter: usize,
+    // Seat whose turn it is, relative to us: 0 is us, n is `team_hands[n - 1]`
+    current_player: usize,
+    // Cards left to draw, the next card to be drawn is last
+    deck: Vec<CardId>,
+    // Identities of cards we can't see (our own hand and the deck), only known
+    // when the game is being driven locally
+    hidden: HashMap<CardId, (Color, usize)>,
+    // Set once the last card is drawn, every player gets one more turn
+    turns_left: Option<usize>,
 }
 
-impl<const P: usize, const H: usize> GameState<P, H> {
+impl GameState {
+    // Including us
+    pub fn players(&self) -> usize {
+        self.team_hands.len() + 1
+    }
+
     pub fn get_card(&self, id: CardId) -> Card {
         if let Some(card) = self.discarded.iter().find(|(cid, _, _)| *cid == id) {
             return Card {
@@ -212,6 +519,18 @@ impl<const P: usize, const H: usize> GameState<P, H> {
         true
     }
 
+    // Whether every card it could be is already on the stacks
+    pub fn all_played(&self, color: ColorFlags, number: NumberFlags) -> bool {
+        if color.is_empty() || number.is_empty() {
+            return false;
+        }
+        color.iter().all(|c| {
+            let c = Color::try_from(c).unwrap();
+            let height = self.played.get(&c).map_or(0, |s| s.len());
+            number.iter().all(|n| usize::try_from(n).unwrap() <= height)
+        })
+    }
+
     pub fn is_critical(&self, color: ColorFlags, number: NumberFlags) -> bool {
         for color in color.iter() {
             let color = Color::try_from(color).unwrap();
@@ -245,6 +564,65 @@ impl<const P: usize, const H: usize> GameState<P, H> {
         false
     }
 
+    // Whether it can never be played: everything it could be is already on the
+    // stacks, or every copy of a lower card in its suit has been discarded.
+    // Duplicates depend on which card it is, so `Card::is_trash` covers those.
+    pub fn is_trash(&self, color: ColorFlags, number: NumberFlags) -> bool {
+        if color.is_empty() || number.is_empty() {
+            return false;
+        }
+
+        color.iter().all(|c| {
+            let c = Color::try_from(c).unwrap();
+            number
+                .iter()
+                .all(|n| self.is_dead(c, usize::try_from(n).unwrap()))
+        })
+    }
+
+    fn is_dead(&self, color: Color, number: usize) -> bool {
+        let height = self.played.get(&color).map_or(0, |s| s.len());
+        number <= height
+            || (1..number).any(|lower| self.discarded_count(color, lower) >= copies(lower))
+    }
+
+    // Whether anyone but the player in `holder` has a touched copy of card `id`
+    // that's the one being kept, counting our own cards only when we know
+    // exactly what they are. When both copies are touched, whichever was drawn
+    // first is kept, so everyone agrees on which one to let go.
+    fn touched_elsewhere(
+        &self,
+        color: Color,
+        number: usize,
+        holder: usize,
+        id: CardId,
+        touched: bool,
+    ) -> bool {
+        let kept = |other: &CardId| !touched || *other < id;
+        let ours = holder != 0
+            && self.player_hand.iter().any(|(other, c, n, t)| {
+                *t && kept(other)
+                    && Color::try_from(*c) == Ok(color)
+                    && usize::try_from(*n) == Ok(number)
+            });
+        let theirs = self
+            .team_hands
+            .iter()
+            .enumerate()
+            .filter(|(player, _)| player + 1 != holder)
+            .flat_map(|(_, h)| h.hand.iter())
+            .any(|(other, c, n, t)| *t && kept(other) && *c == color && *n == number);
+
+        ours || theirs
+    }
+
+    pub(crate) fn discarded_count(&self, color: Color, number: usize) -> usize {
+        self.discarded
+            .iter()
+            .filter(|(_, c, n)| *c == color && *n == number)
+            .count()
+    }
+
     pub fn playable_cards_in_teammate_hands(
         &self,
     ) -> impl Iterator<Item = (CardId, TeammateCard)> + '_ {
@@ -301,7 +679,13 @@ impl<const P: usize, const H: usize> GameState<P, H> {
             })
     }
 
-    pub fn get_focus_for_hint(&self, player: usize, hint: Hint) -> Focus {
+    // Whether a teammate can already tell that one of their cards is playable
+    pub fn teammate_knows_playable(&self, player: usize, index: usize) -> bool {
+        let (_, color, number, _) = self.team_hands[player].empathy[index];
+        !color.is_empty() && !number.is_empty() && self.is_playable(color, number)
+    }
+
+    pub fn get_focus_for_hint(&self, player: usize, hint: Hint) -> Result<Focus, IllegalAction> {
         self.team_hands[player].determine_focus_for_hint(hint)
     }
 
@@ -321,12 +705,49 @@ impl<const P: usize, const H: usize> GameState<P, H> {
     }
 }
 
-pub struct TeammateHand<const H: usize> {
-    hand: [(CardId, Color, usize, bool); H],
-    empathy: [(CardId, ColorFlags, NumberFlags, bool); H],
+#[derive(Clone)]
+pub struct TeammateHand {
+    hand: Vec<(CardId, Color, usize, bool)>,
+    empathy: Vec<(CardId, ColorFlags, NumberFlags, bool)>,
 }
 
-impl<const H: usize> TeammateHand<H> {
+impl TeammateHand {
+    // Keeps track of what they'll have worked out about their cards from a hint
+    // that touched the cards in `slots`
+    pub fn apply_hint(&mut self, hint: Hint, slots: &[usize]) {
+        for (index, card) in self.empathy.iter_mut().enumerate() {
+            apply_hint_to_card(card, hint, slots.contains(&index));
+        }
+        for (index, card) in self.hand.iter_mut().enumerate() {
+            if slots.contains(&index) {
+                card.3 = true;
+            }
+        }
+    }
+
+    // New cards go in the leftmost slot, pushing everything else right
+    pub fn draw(&mut self, id: CardId, color: Color, number: usize) {
+        self.hand.insert(0, (id, color, number, false));
+        self.empathy
+            .insert(0, (id, ColorFlags::all(), NumberFlags::all(), false));
+    }
+
+    // Everything to the left of the card shifts right to fill the gap
+    pub fn remove(&mut self, id: CardId) -> Option<(CardId, Color, usize, bool)> {
+        let index = self.hand.iter().position(|(cid, _, _, _)| *cid == id)?;
+        self.empathy.remove(index);
+        Some(self.hand.remove(index))
+    }
+
+    pub fn new(hand: Vec<(CardId, Color, usize, bool)>) -> Self {
+        let empathy = hand
+            .iter()
+            .map(|(id, _, _, touched)| (*id, ColorFlags::all(), NumberFlags::all(), *touched))
+            .collect();
+
+        Self { hand, empathy }
+    }
+
     fn get_chop(&self) -> Option<(CardId, Color, usize, bool, usize)> {
         self.hand
             .iter()
@@ -334,54 +755,115 @@ impl<const H: usize> TeammateHand<H> {
             .enumerate()
             .map(|(i, (id, c, n, t))| (id, c, n, t, i))
             .filter(|(_, _, _, t, _)| !t)
-            .min_by_key(|(_, _, _, _, index)| *index)
+            .max_by_key(|(_, _, _, _, index)| *index)
     }
 
-    fn determine_focus_for_hint(&self, hint: Hint) -> Focus {
-        let hinted_cards: Vec<_> = self
+    // A hint that doesn't touch anything has no focus, but it can still be
+    // reported to us by the server
+    fn determine_focus_for_hint(&self, hint: Hint) -> Result<Focus, IllegalAction> {
+        let slots: Vec<_> = self
             .hand
             .iter()
-            .copied()
             .enumerate()
-            .map(|(index, (id, c, number, touched))| (id, c, number, touched, index))
-            .filter(|(_, c, n, _, _)| hint.applies_to_card((*c, *n)))
+            .filter(|(_, (_, c, n, _))| hint.applies_to_card((*c, *n)))
+            .map(|(index, _)| index)
             .collect();
-        let chop = self.get_chop();
-
-        let newly_touched_cards: Vec<_> =
-            hinted_cards.iter().filter(|(_, _, _, t, _)| !t).collect();
-        // More than one card touched for the first time?
-        if newly_touched_cards.len() >= 2 {
-            if let Some(chop) = chop {
-                // Was the chop touched and it wasn't touched before?
-                if hinted_cards.iter().any(|c| c.0 == chop.0) && !chop.3 {
-                    // Then the chop was focused
-                    return Focus::Chop(chop.0, chop.4);
-                }
-            }
+        let cards: Vec<_> = self.hand.iter().map(|(id, _, _, t)| (*id, *t)).collect();
+        let chop = self.get_chop().map(|(_, _, _, _, index)| index);
+
+        focus_for_slots(&cards, &slots, chop).ok_or(IllegalAction::EmptyHint)
+    }
+}
+
+// Works out which card a clue touching `slots` was about, from what was
+// touched before it was given
+fn focus_for_slots(
+    cards: &[(CardId, bool)],
+    slots: &[usize],
+    chop: Option<usize>,
+) -> Option<Focus> {
+    // The chop is always the focus when it's touched for the first time
+    if let Some(chop) = chop.filter(|chop| slots.contains(chop)) {
+        return Some(Focus::Chop(cards[chop].0, chop));
+    }
+
+    let newly_touched: Vec<_> = slots
+        .iter()
+        .copied()
+        .filter(|slot| !cards[*slot].1)
+        .collect();
+    let leftmost = match newly_touched[..] {
+        // Only one card was newly touched, so that was the focus
+        [index] => return Some(Focus::NewCard(cards[index].0, index)),
+        // Re-touching only cards that were already touched focuses the leftmost one
+        [] => slots.iter().min()?,
+        _ => newly_touched.iter().min()?,
+    };
+    Some(Focus::LeftMost(cards[*leftmost].0, *leftmost))
+}
+
+// What the thoughts in the queue currently have to say about each card
+#[derive(Default)]
+pub struct Thoughts(HashMap<CardId, Vec<Belief>>);
+
+impl Thoughts {
+    fn add(&mut self, thought: &ThoughtType) {
+        if let (Some(card), Some(belief)) = (thought.card(), thought.belief()) {
+            self.0.entry(card).or_default().push(belief);
         }
+    }
 
-        if let Some((id, _, _, _, index)) = newly_touched_cards.first() {
-            // Only one card was newly touched, so that was the focus
-            return Focus::NewCard(*id, *index);
+    fn remove(&mut self, thought: &ThoughtType) {
+        let (Some(card), Some(belief)) = (thought.card(), thought.belief()) else {
+            return;
+        };
+        let Some(beliefs) = self.0.get_mut(&card) else {
+            return;
+        };
+        if let Some(i) = beliefs.iter().position(|b| *b == belief) {
+            beliefs.remove(i);
         }
+        if beliefs.is_empty() {
+            self.0.remove(&card);
+        }
+    }
 
-        let left_most_card = newly_touched_cards
-            .into_iter()
-            .copied()
-            .min_by_key(|(_, _, _, _, index)| *index)
-            .unwrap();
-        Focus::LeftMost(left_most_card.0, left_most_card.4)
+    pub fn for_card(&self, card: CardId) -> &[Belief] {
+        self.0.get(&card).map_or(&[], |b| b.as_slice())
+    }
+
+    pub fn cards_with_intent(&self, intent: Intent) -> impl Iterator<Item = CardId> + '_ {
+        self.0
+            .iter()
+            .filter(move |(_, beliefs)| beliefs.iter().any(|b| b.intent == intent))
+            .map(|(card, _)| *card)
     }
 }
 
-pub struct Thoughts(HashMap<CardId, Vec<ThoughtType>>);
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Intent {
+    // We mean to play it as soon as we can
+    Play,
+    // It's being kept safe until it can be played
+    Save,
+    // It can be played once the cards connecting to it have been
+    Wait,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Belief {
+    pub thought: &'static str,
+    pub intent: Intent,
+    // The number the card has to be, if the thought tells us that much
+    pub number: Option<usize>,
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct CardId(usize);
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CardId(pub usize);
 
 impl CardId {
-    pub fn resolve<const P: usize, const H: usize>(&self, game_state: &GameState<P, H>) -> Card {
+    pub fn resolve(&self, game_state: &GameState) -> Card {
         game_state.get_card(*self)
     }
 }
@@ -398,10 +880,7 @@ impl std::fmt::Display for Card {
 }
 
 impl Card {
-    pub fn is_playable<const P: usize, const H: usize>(
-        &self,
-        game_state: &GameState<P, H>,
-    ) -> bool {
+    pub fn is_playable(&self, game_state: &GameState) -> bool {
         let (cflags, nflags): (ColorFlags, NumberFlags) = match &self.typ {
             CardType::Played(c, n) => ((*c).into(), (*n).into()),
             CardType::Discarded(c, n) => ((*c).into(), (*n).into()),
@@ -413,10 +892,7 @@ impl Card {
         game_state.is_playable(cflags, nflags)
     }
 
-    pub fn is_critical<const P: usize, const H: usize>(
-        &self,
-        game_state: &GameState<P, H>,
-    ) -> bool {
+    pub fn is_critical(&self, game_state: &GameState) -> bool {
         let (cflags, nflags): (ColorFlags, NumberFlags) = match &self.typ {
             CardType::Played(c, n) => ((*c).into(), (*n).into()),
             CardType::Discarded(c, n) => ((*c).into(), (*n).into()),
@@ -427,6 +903,39 @@ impl Card {
 
         game_state.is_critical(cflags, nflags)
     }
+
+    // A teammate's card is judged from their side, so a touched copy anywhere
+    // but their own hand that's being kept makes it trash. One of our own cards
+    // can only be a duplicate once we know exactly what it is.
+    pub fn is_trash(&self, game_state: &GameState) -> bool {
+        let (cflags, nflags): (ColorFlags, NumberFlags) = match &self.typ {
+            CardType::Played(_, _) => return true,
+            CardType::Discarded(c, n) => ((*c).into(), (*n).into()),
+            CardType::InDeck => return false,
+            CardType::TeamHand(c) => {
+                return game_state.is_dead(c.color, c.number)
+                    || game_state.touched_elsewhere(
+                        c.color,
+                        c.number,
+                        c.player + 1,
+                        self.id,
+                        c.touched,
+                    )
+            }
+            CardType::PlayerHand(c) => {
+                if let (Ok(color), Ok(number)) =
+                    (Color::try_from(c.color), usize::try_from(c.number))
+                {
+                    if game_state.touched_elsewhere(color, number, 0, self.id, c.touched) {
+                        return true;
+                    }
+                }
+                (c.color, c.number)
+            }
+        };
+
+        game_state.is_trash(cflags, nflags)
+    }
 }
 
 pub enum CardType {
@@ -464,18 +973,37 @@ impl std::fmt::Display for CardType {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Action {
     Play(CardId),
     Discard(CardId),
     Hint(usize, Hint),
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Hint {
     Color(Color),
     Number(usize),
 }
 
+fn apply_hint_to_card(
+    card: &mut (CardId, ColorFlags, NumberFlags, bool),
+    hint: Hint,
+    touched: bool,
+) {
+    match hint {
+        Hint::Color(c) if touched => card.1 &= c.into(),
+        Hint::Color(c) => card.1.remove(c.into()),
+        Hint::Number(n) if touched => card.2 &= n.into(),
+        Hint::Number(n) => card.2.remove(n.into()),
+    }
+    if touched {
+        card.3 = true;
+    }
+}
+
 impl Hint {
     fn applies_to_card(&self, card: (Color, usize)) -> bool {
         match self {
@@ -487,6 +1015,7 @@ impl Hint {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Color {
     Blue,
     Yellow,
@@ -495,6 +1024,16 @@ pub enum Color {
     Purple,
 }
 
+impl Color {
+    pub const ALL: [Color; 5] = [
+        Color::Blue,
+        Color::Yellow,
+        Color::Green,
+        Color::Red,
+        Color::Purple,
+    ];
+}
+
 impl std::fmt::Display for Color {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let c = match self {
@@ -613,36 +1152,84 @@ impl std::fmt::Display for NumberFlags {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Focus {
     Chop(CardId, usize),
     NewCard(CardId, usize),
     LeftMost(CardId, usize),
 }
 
-pub struct PlayerHand<const H: usize>([(CardId, ColorFlags, NumberFlags, bool); H]);
-impl<const H: usize> Index<usize> for PlayerHand<H> {
+impl Focus {
+    pub fn id(&self) -> CardId {
+        match self {
+            Focus::Chop(id, _) | Focus::NewCard(id, _) | Focus::LeftMost(id, _) => *id,
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        match self {
+            Focus::Chop(_, index) | Focus::NewCard(_, index) | Focus::LeftMost(_, index) => *index,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct PlayerHand(Vec<(CardId, ColorFlags, NumberFlags, bool)>);
+impl Index<usize> for PlayerHand {
     type Output = (CardId, ColorFlags, NumberFlags, bool);
 
     fn index(&self, index: usize) -> &Self::Output {
         &self.0[index]
     }
 }
-impl<const H: usize> Deref for PlayerHand<H> {
-    type Target = [(CardId, ColorFlags, NumberFlags, bool); H];
+impl Deref for PlayerHand {
+    type Target = [(CardId, ColorFlags, NumberFlags, bool)];
 
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
 
-impl<const H: usize> PlayerHand<H> {
+impl PlayerHand {
+    pub fn new(hand: Vec<(CardId, ColorFlags, NumberFlags, bool)>) -> Self {
+        Self(hand)
+    }
+
+    // Narrows down what we know about every card from a hint that touched the cards in `slots`.
+    // Any card that wasn't touched can't be what was hinted either.
+    pub fn apply_hint(&mut self, hint: Hint, slots: &[usize]) {
+        for (index, card) in self.0.iter_mut().enumerate() {
+            apply_hint_to_card(card, hint, slots.contains(&index));
+        }
+    }
+
+    pub fn draw(&mut self, id: CardId) {
+        self.0
+            .insert(0, (id, ColorFlags::all(), NumberFlags::all(), false));
+    }
+
+    pub fn remove(&mut self, id: CardId) -> Option<(CardId, ColorFlags, NumberFlags, bool)> {
+        let index = self.0.iter().position(|(cid, _, _, _)| *cid == id)?;
+        Some(self.0.remove(index))
+    }
+
+    // We can't see our own cards, so this only goes on which of them were
+    // touched, both by the clue and before it
+    pub fn determine_focus_for_hint(&self, slots: &[usize]) -> Result<Focus, IllegalAction> {
+        let cards: Vec<_> = self.0.iter().map(|(id, _, _, t)| (*id, *t)).collect();
+        let chop = self.get_chop().map(|(_, card)| card.index);
+
+        focus_for_slots(&cards, slots, chop).ok_or(IllegalAction::EmptyHint)
+    }
+
+    // The oldest card that hasn't been touched, on the right of the hand
     pub fn get_chop(&self) -> Option<(CardId, PlayerCard)> {
         self.0
             .iter()
             .copied()
             .enumerate()
             .filter(|(_, (_, _, _, touched))| !touched)
-            .min_by_key(|(index, _)| *index)
+            .max_by_key(|(index, _)| *index)
             .map(|(index, (id, color, number, touched))| {
                 (
                     id,
@@ -1,17 +1,187 @@
-use std::{str::FromStr, time::Duration};
+use std::{
+    collections::HashMap,
+    io::{Read, Write},
+    str::FromStr,
+    time::Duration,
+};
 
-use serde::Deserialize;
+use analytical::{
+    protocol::{
+        card_identity, suit_index, ActionCommand, ActionType, ClientMessage, ClueType, GameAction,
+        Init, ServerMessage, TableId, TableJoin, SUITS,
+    },
+    *,
+};
+use tungstenite::{client::IntoClientRequest, http::HeaderValue, Message, WebSocket};
 
-use analytical::*;
-use tungstenite::{client::IntoClientRequest, http::HeaderValue, Message};
+const DECK_SIZE: usize = 50;
 
-pub enum HanabMessage {
-    Welcome(WelcomeMessage),
+// A game we've been seated at
+pub struct Session {
+    table_id: u64,
+    our_index: usize,
+    players: usize,
+    state: GameState,
+    brain: Brain,
+    loaded: bool,
+    our_turn: bool,
 }
 
-#[derive(Debug, Deserialize)]
-pub struct WelcomeMessage {
-    username: String,
+impl Session {
+    pub fn new(init: &Init) -> color_eyre::Result<Self> {
+        let players = init.player_names.len();
+        // Cards are drawn in order, so we know the ids of everything left in the deck
+        let mut builder = GameState::builder()
+            .player_hand(PlayerHand::new(Vec::new()))
+            .deck((0..DECK_SIZE).rev().map(CardId).collect());
+        for _ in 1..players {
+            builder = builder.teammate_hand(TeammateHand::new(Vec::new()));
+        }
+
+        Ok(Self {
+            table_id: init.table_id,
+            our_index: init.our_player_index,
+            players,
+            state: builder.build()?,
+            brain: Brain::new(),
+            loaded: false,
+            our_turn: false,
+        })
+    }
+
+    fn seat(&self, player_index: usize) -> usize {
+        (player_index + self.players - self.our_index) % self.players
+    }
+
+    pub fn handle(&mut self, action: GameAction) -> color_eyre::Result<()> {
+        let event = match action {
+            GameAction::Draw {
+                player_index,
+                order,
+                suit_index,
+                rank,
+            } => GameEvent::Draw(
+                self.seat(player_index),
+                CardId(order),
+                card_identity(suit_index, rank),
+            ),
+            GameAction::Play {
+                player_index,
+                order,
+                suit_index,
+                rank,
+            } => {
+                let (color, number) = card_identity(suit_index, rank).ok_or_else(|| {
+                    color_eyre::eyre::eyre!("played card {order} wasn't revealed")
+                })?;
+                GameEvent::Play(self.seat(player_index), CardId(order), color, number)
+            }
+            GameAction::Discard {
+                player_index,
+                order,
+                suit_index,
+                rank,
+                failed,
+            } => {
+                let (color, number) = card_identity(suit_index, rank).ok_or_else(|| {
+                    color_eyre::eyre::eyre!("discarded card {order} wasn't revealed")
+                })?;
+                if failed {
+                    GameEvent::Bomb(self.seat(player_index), CardId(order), color, number)
+                } else {
+                    GameEvent::Discard(self.seat(player_index), CardId(order), color, number)
+                }
+            }
+            GameAction::Clue {
+                clue,
+                giver,
+                list,
+                target,
+                ..
+            } => {
+                let hint = match clue.typ {
+                    ClueType::Color => {
+                        Hint::Color(*SUITS.get(clue.value).ok_or_else(|| {
+                            color_eyre::eyre::eyre!("unknown color {}", clue.value)
+                        })?)
+                    }
+                    ClueType::Rank => Hint::Number(clue.value),
+                };
+                GameEvent::Hint(
+                    self.seat(giver),
+                    self.seat(target),
+                    hint,
+                    list.into_iter().map(CardId).collect(),
+                )
+            }
+            GameAction::Turn {
+                num,
+                current_player_index,
+            } => {
+                // The game's over once nobody is left to play
+                let Ok(current) = usize::try_from(current_player_index) else {
+                    self.our_turn = false;
+                    return Ok(());
+                };
+                self.our_turn = current == self.our_index;
+                GameEvent::Turn(num, self.seat(current))
+            }
+            GameAction::Strike { num, .. } => {
+                log::info!("Strike {num}!");
+                return Ok(());
+            }
+            GameAction::Status { clues, score, .. } => {
+                log::info!("Clues: {clues}, Score: {score}");
+                if clues != self.state.hint_count() {
+                    log::warn!(
+                        "We think there are {} clues, but the server says {clues}",
+                        self.state.hint_count()
+                    );
+                }
+                return Ok(());
+            }
+            GameAction::GameOver { .. } => {
+                log::info!("Game over! Final score: {}", self.state.score());
+                self.our_turn = false;
+                return Ok(());
+            }
+            GameAction::Unknown => return Ok(()),
+        };
+        self.brain.observe(&event, &self.state);
+        self.state.record(&event)?;
+
+        Ok(())
+    }
+
+    pub fn action_command(&self, action: Action) -> ClientMessage {
+        let target = |player: usize| (self.our_index + player + 1) % self.players;
+        let (typ, target, value) = match action {
+            Action::Play(id) => (ActionType::Play, id.0, None),
+            Action::Discard(id) => (ActionType::Discard, id.0, None),
+            Action::Hint(player, Hint::Color(color)) => (
+                ActionType::ColorClue,
+                target(player),
+                Some(suit_index(color)),
+            ),
+            Action::Hint(player, Hint::Number(number)) => {
+                (ActionType::RankClue, target(player), Some(number))
+            }
+        };
+
+        ClientMessage::Action(ActionCommand {
+            table_id: self.table_id,
+            typ,
+            target,
+            value,
+        })
+    }
+}
+
+fn send<S: Read + Write>(socket: &mut WebSocket<S>, msg: ClientMessage) -> color_eyre::Result<()> {
+    log::debug!("Sending: {msg}");
+    socket.send(Message::Text(msg.to_string()))?;
+
+    Ok(())
 }
 
 pub fn backoff<R, E: std::fmt::Display, F: Fn() -> Result<R, E>>(
@@ -36,30 +206,6 @@ pub fn backoff<R, E: std::fmt::Display, F: Fn() -> Result<R, E>>(
     }
 }
 
-impl FromStr for HanabMessage {
-    type Err = color_eyre::Report;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut iter = s.split(' ');
-        let msg_type = iter
-            .next()
-            .ok_or_else(|| color_eyre::eyre::eyre!("No msg type sent from server"))?;
-        match msg_type {
-            "welcome" => {
-                let s = iter.fold(String::new(), |mut a, b| {
-                    a.reserve(b.len() + 1);
-                    a.push_str(b);
-                    a.push_str(" ");
-                    a
-                });
-                let data: WelcomeMessage = serde_json::from_str(s.trim_end())?;
-                Ok(HanabMessage::Welcome(data))
-            }
-            v => Err(color_eyre::eyre::eyre!("unsupported message type: {v}")),
-        }
-    }
-}
-
 fn main() -> color_eyre::Result<()> {
     pretty_env_logger::init();
     color_eyre::install()?;
@@ -139,17 +285,120 @@ fn main() -> color_eyre::Result<()> {
 
     let (mut socket, _) = tungstenite::connect(ws_request)?;
 
+    let mut our_username = String::new();
+    let mut tables: HashMap<u64, Vec<String>> = HashMap::new();
+    let mut session: Option<Session> = None;
+
     while let Ok(msg) = socket.read() {
         if let Message::Text(msg) = msg {
             log::debug!("Message received: {msg}");
-            match HanabMessage::from_str(&msg) {
-                Ok(msg) => match msg {
-                    HanabMessage::Welcome(msg) => {
-                        log::info!("Username: {}", msg.username)
-                    }
-                },
+            let msg = match ServerMessage::from_str(&msg) {
+                Ok(msg) => msg,
                 Err(e) => {
                     log::error!("Error receiving msg: {e}");
+                    continue;
+                }
+            };
+            match msg {
+                ServerMessage::Welcome(msg) => {
+                    log::info!("Username: {}", msg.username);
+                    our_username = msg.username;
+                }
+                ServerMessage::Warning(msg) => log::warn!("Server warning: {}", msg.warning),
+                ServerMessage::Error(msg) => log::error!("Server error: {}", msg.error),
+                ServerMessage::Table(table) => {
+                    tables.insert(table.id, table.players);
+                }
+                ServerMessage::TableList(list) => {
+                    tables.extend(list.into_iter().map(|t| (t.id, t.players)));
+                }
+                ServerMessage::TableGone(msg) => {
+                    tables.remove(&msg.table_id);
+                }
+                ServerMessage::Chat(chat) => {
+                    // Players invite us by messaging us "/join" from their table
+                    if chat.recipient != our_username || chat.msg.trim() != "/join" {
+                        continue;
+                    }
+                    match tables
+                        .iter()
+                        .find(|(_, players)| players.contains(&chat.who))
+                    {
+                        Some((id, _)) => {
+                            log::info!("Joining {}'s table {id}", chat.who);
+                            send(
+                                &mut socket,
+                                ClientMessage::TableJoin(TableJoin {
+                                    table_id: *id,
+                                    password: String::new(),
+                                }),
+                            )?;
+                        }
+                        None => log::warn!("{} asked us to join, but isn't at a table", chat.who),
+                    }
+                }
+                ServerMessage::TableStart(msg) => {
+                    send(
+                        &mut socket,
+                        ClientMessage::GetGameInfo1(TableId {
+                            table_id: msg.table_id,
+                        }),
+                    )?;
+                }
+                ServerMessage::Init(msg) => {
+                    log::info!("Starting a game with {:?}", msg.player_names);
+                    session = match Session::new(&msg) {
+                        Ok(s) => Some(s),
+                        Err(e) => {
+                            log::error!("Couldn't start the game: {e}");
+                            None
+                        }
+                    };
+                    send(
+                        &mut socket,
+                        ClientMessage::GetGameInfo2(TableId {
+                            table_id: msg.table_id,
+                        }),
+                    )?;
+                }
+                ServerMessage::GameActionList(msg) => {
+                    let Some(s) = session.as_mut().filter(|s| s.table_id == msg.table_id) else {
+                        continue;
+                    };
+                    for action in msg.list {
+                        if let Err(e) = s.handle(action) {
+                            log::error!("Error handling action: {e}");
+                        }
+                    }
+                    s.loaded = true;
+                    send(
+                        &mut socket,
+                        ClientMessage::Loaded(TableId {
+                            table_id: msg.table_id,
+                        }),
+                    )?;
+                }
+                ServerMessage::GameAction(msg) => {
+                    let Some(s) = session.as_mut().filter(|s| s.table_id == msg.table_id) else {
+                        continue;
+                    };
+                    if let Err(e) = s.handle(msg.action) {
+                        log::error!("Error handling action: {e}");
+                    }
+                }
+                ServerMessage::GameOver(msg) => {
+                    if session.as_ref().is_some_and(|s| s.table_id == msg.table_id) {
+                        session = None;
+                    }
+                }
+                _ => {}
+            }
+
+            if let Some(s) = session.as_mut().filter(|s| s.loaded && s.our_turn) {
+                s.our_turn = false;
+                match s.brain.play(&s.state) {
+                    Ok(action) => send(&mut socket, s.action_command(action))?,
+                    Err(e) => log::error!("Couldn't come up with anything to do: {e}"),
                 }
             }
         }
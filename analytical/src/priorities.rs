@@ -0,0 +1,73 @@
+use crate::ThoughtType;
+
+// How soon the Brain gets around to each kind of thought, lower numbers are
+// thought about first. Thoughts with the same priority are thought about in the
+// order they were had.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct Priorities {
+    pub prompt: usize,
+    pub prompted: usize,
+    pub finesse: usize,
+    pub finessed: usize,
+    pub bluff: usize,
+    pub bluffed: usize,
+    pub play: usize,
+    pub five_save: usize,
+    pub two_save: usize,
+    pub save: usize,
+    pub five_stall: usize,
+    pub early_game: usize,
+    pub discard: usize,
+}
+
+impl Priorities {
+    pub fn of(&self, thought: &ThoughtType) -> usize {
+        match thought {
+            ThoughtType::Prompt(_) => self.prompt,
+            ThoughtType::Prompted(_) => self.prompted,
+            ThoughtType::Finesse(_) => self.finesse,
+            ThoughtType::Finessed(_) => self.finessed,
+            ThoughtType::Bluff(_) => self.bluff,
+            ThoughtType::Bluffed(_) => self.bluffed,
+            ThoughtType::Play(_) => self.play,
+            ThoughtType::FiveSave(_) => self.five_save,
+            ThoughtType::TwoSave(_) => self.two_save,
+            ThoughtType::Save(_) => self.save,
+            ThoughtType::FiveStall(_) => self.five_stall,
+            ThoughtType::EarlyGame(_) => self.early_game,
+            ThoughtType::Discard(_) => self.discard,
+        }
+    }
+
+    // Anything missing from `json` keeps its default priority, and anything
+    // we don't recognise is an error rather than a silently ignored typo
+    #[cfg(all(feature = "serde", feature = "serde_json"))]
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+impl Default for Priorities {
+    fn default() -> Self {
+        Self {
+            prompt: 20,
+            prompted: 20,
+            finesse: 10,
+            finessed: 10,
+            bluff: 10,
+            bluffed: 10,
+            play: 5,
+            five_save: 6,
+            two_save: 6,
+            save: 9,
+            five_stall: 1,
+            early_game: 10,
+            discard: 0,
+        }
+    }
+}
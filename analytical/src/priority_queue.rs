@@ -24,7 +24,25 @@ impl<K: Eq + Ord + Hash + Copy, V> PriorityQueue<K, V> {
     pub fn pop(&mut self) -> Option<V> {
         let lowest_priority = *self.inner.keys().min()?;
         let v = self.inner.get_mut(&lowest_priority)?;
-        v.pop()
+        // First in, first out, the same end `peek` looks at
+        let item = (!v.is_empty()).then(|| v.remove(0));
+        // Don't let an empty priority hide the ones after it
+        if v.is_empty() {
+            self.inner.remove(&lowest_priority);
+        }
+        item
+    }
+
+    // Takes out every item `f` picks, whatever its priority
+    pub fn remove_if(&mut self, mut f: impl FnMut(&V) -> bool) -> Vec<V> {
+        let mut removed = Vec::new();
+        for items in self.inner.values_mut() {
+            let (out, kept) = std::mem::take(items).into_iter().partition(&mut f);
+            *items = kept;
+            removed.extend::<Vec<V>>(out);
+        }
+        self.inner.retain(|_, items| !items.is_empty());
+        removed
     }
 
     pub fn peek(&self) -> Option<&V> {
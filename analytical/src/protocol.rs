@@ -0,0 +1,536 @@
+// The messages sent over hanab.live's websocket.
+//
+// Every frame is a command name, a space, and then the command's data as JSON.
+
+use std::{fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::Color;
+
+// The order hanab.live lists the suits in for "No Variant"
+pub const SUITS: [Color; 5] = [
+    Color::Red,
+    Color::Yellow,
+    Color::Green,
+    Color::Blue,
+    Color::Purple,
+];
+
+pub fn suit_index(color: Color) -> usize {
+    SUITS.iter().position(|c| *c == color).unwrap()
+}
+
+// Cards we can't see are sent with a suit and rank of -1
+pub fn card_identity(suit_index: i32, rank: i32) -> Option<(Color, usize)> {
+    let color = *SUITS.get(usize::try_from(suit_index).ok()?)?;
+    let number = usize::try_from(rank).ok()?;
+
+    Some((color, number))
+}
+
+#[derive(Debug)]
+pub enum ProtocolError {
+    NoCommand,
+    UnknownCommand(String),
+    Json(serde_json::Error),
+}
+
+impl Display for ProtocolError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProtocolError::NoCommand => write!(f, "no command was sent"),
+            ProtocolError::UnknownCommand(c) => write!(f, "unsupported command: {c}"),
+            ProtocolError::Json(e) => write!(f, "invalid command data: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ProtocolError {}
+
+impl From<serde_json::Error> for ProtocolError {
+    fn from(value: serde_json::Error) -> Self {
+        ProtocolError::Json(value)
+    }
+}
+
+// Commands the server sends us
+#[derive(Debug, Clone, PartialEq)]
+pub enum ServerMessage {
+    Welcome(Welcome),
+    Warning(Warning),
+    Error(ErrorMessage),
+    User(User),
+    UserList(Vec<User>),
+    UserLeft(UserLeft),
+    TableList(Vec<Table>),
+    Table(Table),
+    TableGone(TableId),
+    TableStart(TableStart),
+    Joined(TableId),
+    Left,
+    Chat(Chat),
+    Init(Init),
+    GameAction(GameActionMessage),
+    GameActionList(GameActionList),
+    Connected(Connected),
+    GameOver(TableId),
+}
+
+// Commands we send to the server
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClientMessage {
+    TableCreate(TableCreate),
+    TableJoin(TableJoin),
+    TableStart(TableId),
+    TableUnattend(TableId),
+    GetGameInfo1(TableId),
+    GetGameInfo2(TableId),
+    Loaded(TableId),
+    Action(ActionCommand),
+    Chat(ChatSend),
+    ChatPm(ChatPm),
+}
+
+fn split_command(s: &str) -> Result<(&str, &str), ProtocolError> {
+    let (command, data) = s.split_once(' ').unwrap_or((s, "{}"));
+    if command.is_empty() {
+        return Err(ProtocolError::NoCommand);
+    }
+
+    Ok((command, data))
+}
+
+impl FromStr for ServerMessage {
+    type Err = ProtocolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (command, data) = split_command(s)?;
+        let msg = match command {
+            "welcome" => ServerMessage::Welcome(serde_json::from_str(data)?),
+            "warning" => ServerMessage::Warning(serde_json::from_str(data)?),
+            "error" => ServerMessage::Error(serde_json::from_str(data)?),
+            "user" => ServerMessage::User(serde_json::from_str(data)?),
+            "userList" => ServerMessage::UserList(serde_json::from_str(data)?),
+            "userLeft" => ServerMessage::UserLeft(serde_json::from_str(data)?),
+            "tableList" => ServerMessage::TableList(serde_json::from_str(data)?),
+            "table" => ServerMessage::Table(serde_json::from_str(data)?),
+            "tableGone" => ServerMessage::TableGone(serde_json::from_str(data)?),
+            "tableStart" => ServerMessage::TableStart(serde_json::from_str(data)?),
+            "joined" => ServerMessage::Joined(serde_json::from_str(data)?),
+            "left" => ServerMessage::Left,
+            "chat" => ServerMessage::Chat(serde_json::from_str(data)?),
+            "init" => ServerMessage::Init(serde_json::from_str(data)?),
+            "gameAction" => ServerMessage::GameAction(serde_json::from_str(data)?),
+            "gameActionList" => ServerMessage::GameActionList(serde_json::from_str(data)?),
+            "connected" => ServerMessage::Connected(serde_json::from_str(data)?),
+            "gameOver" => ServerMessage::GameOver(serde_json::from_str(data)?),
+            c => return Err(ProtocolError::UnknownCommand(c.to_string())),
+        };
+
+        Ok(msg)
+    }
+}
+
+impl Display for ServerMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (command, data) = match self {
+            ServerMessage::Welcome(d) => ("welcome", serde_json::to_string(d)),
+            ServerMessage::Warning(d) => ("warning", serde_json::to_string(d)),
+            ServerMessage::Error(d) => ("error", serde_json::to_string(d)),
+            ServerMessage::User(d) => ("user", serde_json::to_string(d)),
+            ServerMessage::UserList(d) => ("userList", serde_json::to_string(d)),
+            ServerMessage::UserLeft(d) => ("userLeft", serde_json::to_string(d)),
+            ServerMessage::TableList(d) => ("tableList", serde_json::to_string(d)),
+            ServerMessage::Table(d) => ("table", serde_json::to_string(d)),
+            ServerMessage::TableGone(d) => ("tableGone", serde_json::to_string(d)),
+            ServerMessage::TableStart(d) => ("tableStart", serde_json::to_string(d)),
+            ServerMessage::Joined(d) => ("joined", serde_json::to_string(d)),
+            ServerMessage::Left => ("left", Ok("{}".to_string())),
+            ServerMessage::Chat(d) => ("chat", serde_json::to_string(d)),
+            ServerMessage::Init(d) => ("init", serde_json::to_string(d)),
+            ServerMessage::GameAction(d) => ("gameAction", serde_json::to_string(d)),
+            ServerMessage::GameActionList(d) => ("gameActionList", serde_json::to_string(d)),
+            ServerMessage::Connected(d) => ("connected", serde_json::to_string(d)),
+            ServerMessage::GameOver(d) => ("gameOver", serde_json::to_string(d)),
+        };
+
+        write!(f, "{command} {}", data.map_err(|_| std::fmt::Error)?)
+    }
+}
+
+impl FromStr for ClientMessage {
+    type Err = ProtocolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (command, data) = split_command(s)?;
+        let msg = match command {
+            "tableCreate" => ClientMessage::TableCreate(serde_json::from_str(data)?),
+            "tableJoin" => ClientMessage::TableJoin(serde_json::from_str(data)?),
+            "tableStart" => ClientMessage::TableStart(serde_json::from_str(data)?),
+            "tableUnattend" => ClientMessage::TableUnattend(serde_json::from_str(data)?),
+            "getGameInfo1" => ClientMessage::GetGameInfo1(serde_json::from_str(data)?),
+            "getGameInfo2" => ClientMessage::GetGameInfo2(serde_json::from_str(data)?),
+            "loaded" => ClientMessage::Loaded(serde_json::from_str(data)?),
+            "action" => ClientMessage::Action(serde_json::from_str(data)?),
+            "chat" => ClientMessage::Chat(serde_json::from_str(data)?),
+            "chatPM" => ClientMessage::ChatPm(serde_json::from_str(data)?),
+            c => return Err(ProtocolError::UnknownCommand(c.to_string())),
+        };
+
+        Ok(msg)
+    }
+}
+
+impl Display for ClientMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (command, data) = match self {
+            ClientMessage::TableCreate(d) => ("tableCreate", serde_json::to_string(d)),
+            ClientMessage::TableJoin(d) => ("tableJoin", serde_json::to_string(d)),
+            ClientMessage::TableStart(d) => ("tableStart", serde_json::to_string(d)),
+            ClientMessage::TableUnattend(d) => ("tableUnattend", serde_json::to_string(d)),
+            ClientMessage::GetGameInfo1(d) => ("getGameInfo1", serde_json::to_string(d)),
+            ClientMessage::GetGameInfo2(d) => ("getGameInfo2", serde_json::to_string(d)),
+            ClientMessage::Loaded(d) => ("loaded", serde_json::to_string(d)),
+            ClientMessage::Action(d) => ("action", serde_json::to_string(d)),
+            ClientMessage::Chat(d) => ("chat", serde_json::to_string(d)),
+            ClientMessage::ChatPm(d) => ("chatPM", serde_json::to_string(d)),
+        };
+
+        write!(f, "{command} {}", data.map_err(|_| std::fmt::Error)?)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableId {
+    #[serde(rename = "tableID")]
+    pub table_id: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Welcome {
+    #[serde(rename = "userID")]
+    pub user_id: u64,
+    pub username: String,
+    #[serde(default)]
+    pub total_games: u64,
+    #[serde(default)]
+    pub muted: bool,
+    #[serde(default)]
+    pub at_ongoing_table: bool,
+    #[serde(default)]
+    pub shutting_down: bool,
+    #[serde(default)]
+    pub maintenance_mode: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Warning {
+    pub warning: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorMessage {
+    pub error: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct User {
+    #[serde(rename = "userID")]
+    pub user_id: u64,
+    pub name: String,
+    #[serde(default)]
+    pub status: u8,
+    #[serde(default, rename = "tableID")]
+    pub table_id: u64,
+    #[serde(default)]
+    pub hyphenated: bool,
+    #[serde(default)]
+    pub inactive: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserLeft {
+    #[serde(rename = "userID")]
+    pub user_id: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Table {
+    pub id: u64,
+    pub name: String,
+    #[serde(default)]
+    pub password_protected: bool,
+    #[serde(default)]
+    pub joined: bool,
+    pub num_players: usize,
+    #[serde(default)]
+    pub owned: bool,
+    #[serde(default)]
+    pub running: bool,
+    #[serde(default)]
+    pub variant: String,
+    #[serde(default)]
+    pub timed: bool,
+    #[serde(default)]
+    pub shared_replay: bool,
+    #[serde(default)]
+    pub progress: usize,
+    pub players: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableStart {
+    #[serde(rename = "tableID")]
+    pub table_id: u64,
+    #[serde(default)]
+    pub replay: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Chat {
+    pub msg: String,
+    pub who: String,
+    #[serde(default)]
+    pub discord: bool,
+    #[serde(default)]
+    pub server: bool,
+    #[serde(default)]
+    pub datetime: String,
+    #[serde(default)]
+    pub room: String,
+    #[serde(default)]
+    pub recipient: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Init {
+    #[serde(rename = "tableID")]
+    pub table_id: u64,
+    pub player_names: Vec<String>,
+    pub our_player_index: usize,
+    #[serde(default)]
+    pub spectating: bool,
+    #[serde(default)]
+    pub shadowing: bool,
+    #[serde(default)]
+    pub replay: bool,
+    #[serde(default)]
+    pub seed: String,
+    pub options: GameOptions,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameOptions {
+    #[serde(default)]
+    pub num_players: usize,
+    #[serde(default)]
+    pub starting_player: usize,
+    #[serde(default = "no_variant")]
+    pub variant_name: String,
+    #[serde(default)]
+    pub timed: bool,
+}
+
+fn no_variant() -> String {
+    "No Variant".to_string()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameActionMessage {
+    #[serde(rename = "tableID")]
+    pub table_id: u64,
+    pub action: GameAction,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameActionList {
+    #[serde(rename = "tableID")]
+    pub table_id: u64,
+    pub list: Vec<GameAction>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum GameAction {
+    #[serde(rename_all = "camelCase")]
+    Draw {
+        player_index: usize,
+        order: usize,
+        suit_index: i32,
+        rank: i32,
+    },
+    #[serde(rename_all = "camelCase")]
+    Play {
+        player_index: usize,
+        order: usize,
+        suit_index: i32,
+        rank: i32,
+    },
+    #[serde(rename_all = "camelCase")]
+    Discard {
+        player_index: usize,
+        order: usize,
+        suit_index: i32,
+        rank: i32,
+        failed: bool,
+    },
+    Clue {
+        clue: Clue,
+        giver: usize,
+        list: Vec<usize>,
+        target: usize,
+        #[serde(default)]
+        turn: usize,
+    },
+    Strike {
+        num: usize,
+        order: usize,
+        #[serde(default)]
+        turn: usize,
+    },
+    #[serde(rename_all = "camelCase")]
+    Turn {
+        num: usize,
+        current_player_index: i32,
+    },
+    #[serde(rename_all = "camelCase")]
+    Status {
+        clues: usize,
+        score: usize,
+        max_score: usize,
+    },
+    #[serde(rename_all = "camelCase")]
+    GameOver {
+        end_condition: u8,
+        player_index: i32,
+    },
+    // Anything newer than we know how to handle
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Clue {
+    #[serde(rename = "type")]
+    pub typ: ClueType,
+    pub value: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "u8", try_from = "u8")]
+pub enum ClueType {
+    Color,
+    Rank,
+}
+
+impl From<ClueType> for u8 {
+    fn from(value: ClueType) -> Self {
+        match value {
+            ClueType::Color => 0,
+            ClueType::Rank => 1,
+        }
+    }
+}
+
+impl TryFrom<u8> for ClueType {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(ClueType::Color),
+            1 => Ok(ClueType::Rank),
+            v => Err(format!("unknown clue type {v}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Connected {
+    #[serde(rename = "tableID")]
+    pub table_id: u64,
+    pub list: Vec<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableCreate {
+    pub name: String,
+    pub options: GameOptions,
+    #[serde(default)]
+    pub password: String,
+    pub max_players: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableJoin {
+    #[serde(rename = "tableID")]
+    pub table_id: u64,
+    #[serde(default)]
+    pub password: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActionCommand {
+    #[serde(rename = "tableID")]
+    pub table_id: u64,
+    #[serde(rename = "type")]
+    pub typ: ActionType,
+    // The card's order for plays and discards, the player's index for clues
+    pub target: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "u8", try_from = "u8")]
+pub enum ActionType {
+    Play,
+    Discard,
+    ColorClue,
+    RankClue,
+}
+
+impl From<ActionType> for u8 {
+    fn from(value: ActionType) -> Self {
+        match value {
+            ActionType::Play => 0,
+            ActionType::Discard => 1,
+            ActionType::ColorClue => 2,
+            ActionType::RankClue => 3,
+        }
+    }
+}
+
+impl TryFrom<u8> for ActionType {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(ActionType::Play),
+            1 => Ok(ActionType::Discard),
+            2 => Ok(ActionType::ColorClue),
+            3 => Ok(ActionType::RankClue),
+            v => Err(format!("unknown action type {v}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChatSend {
+    pub msg: String,
+    pub room: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChatPm {
+    pub msg: String,
+    pub recipient: String,
+    pub room: String,
+}
@@ -0,0 +1,335 @@
+use crate::{Action, CardId, Color, GameState, Hint};
+
+pub const MAX_HINTS: usize = 8;
+pub const MAX_STRIKES: usize = 3;
+pub const MAX_SCORE: usize = 25;
+
+pub const MIN_PLAYERS: usize = 2;
+pub const MAX_PLAYERS: usize = 6;
+
+// How many cards each player is dealt
+pub fn hand_size(players: usize) -> usize {
+    match players {
+        2 | 3 => 5,
+        4 | 5 => 4,
+        _ => 3,
+    }
+}
+
+// How many copies of each number there are of every color
+pub fn copies(number: usize) -> usize {
+    match number {
+        1 => 3,
+        5 => 1,
+        _ => 2,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Played(CardId, Color, usize),
+    Bombed(CardId, Color, usize),
+    Discarded(CardId, Color, usize),
+    Hinted(usize, Vec<CardId>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameStatus {
+    InProgress,
+    Won(usize),
+    LostToStrikes(usize),
+    EndedByDeck(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IllegalAction {
+    GameOver,
+    NotInHand(CardId),
+    UnknownCard(CardId),
+    NoSuchPlayer(usize),
+    NoHintsLeft,
+    HintsFull,
+    EmptyHint,
+    // Nothing at all can be done, so the Brain has nothing to suggest
+    NoLegalAction,
+}
+
+impl std::fmt::Display for IllegalAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IllegalAction::GameOver => write!(f, "the game is already over"),
+            IllegalAction::NotInHand(id) => {
+                write!(f, "card {id:?} is not in the current player's hand")
+            }
+            IllegalAction::UnknownCard(id) => write!(f, "the identity of card {id:?} is unknown"),
+            IllegalAction::NoSuchPlayer(p) => write!(f, "there is no player {p} to hint"),
+            IllegalAction::NoHintsLeft => write!(f, "there are no hints left"),
+            IllegalAction::HintsFull => write!(f, "can't discard while all hints are available"),
+            IllegalAction::EmptyHint => write!(f, "the hint doesn't touch any cards"),
+            IllegalAction::NoLegalAction => write!(f, "there is no legal action to take"),
+        }
+    }
+}
+
+impl std::error::Error for IllegalAction {}
+
+impl GameState {
+    // Applies the action of the player whose turn it is.
+    //
+    // Hints are relative to the acting player, the same way `Brain::play` gives
+    // them: `Action::Hint(0, _)` is for the player after them.
+    pub fn apply(&mut self, action: Action) -> Result<Outcome, IllegalAction> {
+        if self.status() != GameStatus::InProgress {
+            return Err(IllegalAction::GameOver);
+        }
+
+        let outcome = match action {
+            Action::Play(id) => {
+                let (color, number) = self.take_card(id)?;
+                if self.is_playable(color.into(), number.into()) {
+                    self.played.entry(color).or_default().push((id, number));
+                    if number == 5 && self.hint_count < MAX_HINTS {
+                        self.hint_count += 1;
+                    }
+                    Outcome::Played(id, color, number)
+                } else {
+                    self.discarded.push((id, color, number));
+                    self.strikes += 1;
+                    Outcome::Bombed(id, color, number)
+                }
+            }
+            Action::Discard(id) => {
+                if self.hint_count == MAX_HINTS {
+                    return Err(IllegalAction::HintsFull);
+                }
+                let (color, number) = self.take_card(id)?;
+                self.discarded.push((id, color, number));
+                self.hint_count += 1;
+                Outcome::Discarded(id, color, number)
+            }
+            Action::Hint(player, hint) => {
+                if self.hint_count == 0 {
+                    return Err(IllegalAction::NoHintsLeft);
+                }
+                if player >= self.team_hands.len() {
+                    return Err(IllegalAction::NoSuchPlayer(player));
+                }
+                let seat = (self.current_player + 1 + player) % self.players();
+                let touched = self.cards_touched_by(seat, hint)?;
+                if touched.is_empty() {
+                    return Err(IllegalAction::EmptyHint);
+                }
+                self.receive_hint(seat, hint, &touched);
+                self.hint_count -= 1;
+                Outcome::Hinted(seat, touched)
+            }
+        };
+
+        self.turns_left = match self.turns_left {
+            Some(turns) => Some(turns - 1),
+            None if self.deck.is_empty() => Some(self.players()),
+            None => None,
+        };
+        self.turn_counter += 1;
+        self.current_player = (self.current_player + 1) % self.players();
+
+        Ok(outcome)
+    }
+
+    // Whether the player whose turn it is could take `action`, without taking it
+    pub fn check(&self, action: Action) -> Result<(), IllegalAction> {
+        if self.status() != GameStatus::InProgress {
+            return Err(IllegalAction::GameOver);
+        }
+
+        match action {
+            Action::Play(id) | Action::Discard(id) => {
+                if let Action::Discard(_) = action {
+                    if self.hint_count == MAX_HINTS {
+                        return Err(IllegalAction::HintsFull);
+                    }
+                }
+                if !self.hand_ids(self.current_player).any(|cid| cid == id) {
+                    return Err(IllegalAction::NotInHand(id));
+                }
+            }
+            Action::Hint(player, hint) => {
+                if self.hint_count == 0 {
+                    return Err(IllegalAction::NoHintsLeft);
+                }
+                if player >= self.team_hands.len() {
+                    return Err(IllegalAction::NoSuchPlayer(player));
+                }
+                let seat = (self.current_player + 1 + player) % self.players();
+                if self.cards_touched_by(seat, hint)?.is_empty() {
+                    return Err(IllegalAction::EmptyHint);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn status(&self) -> GameStatus {
+        let score = self.score();
+        if self.strikes >= MAX_STRIKES {
+            // Striking out loses all the points
+            GameStatus::LostToStrikes(0)
+        } else if score == MAX_SCORE {
+            GameStatus::Won(score)
+        } else if self.turns_left == Some(0) {
+            GameStatus::EndedByDeck(score)
+        } else {
+            GameStatus::InProgress
+        }
+    }
+
+    pub fn score(&self) -> usize {
+        self.played.values().map(|stack| stack.len()).sum()
+    }
+
+    pub fn hint_count(&self) -> usize {
+        self.hint_count
+    }
+
+    pub fn strikes(&self) -> usize {
+        self.strikes
+    }
+
+    pub fn cards_left(&self) -> usize {
+        self.deck.len()
+    }
+
+    // How many turns are left once the deck has run out, `None` while there are still cards to draw
+    pub fn turns_left(&self) -> Option<usize> {
+        self.turns_left
+    }
+
+    // Removes a card from the current player's hand, replacing it with one from the deck
+    fn take_card(&mut self, id: CardId) -> Result<(Color, usize), IllegalAction> {
+        let seat = self.current_player;
+        let index = self
+            .hand_ids(seat)
+            .position(|cid| cid == id)
+            .ok_or(IllegalAction::NotInHand(id))?;
+        let identity = self.identity_in_seat(seat, index)?;
+        // We need to know what a teammate is drawing before we touch anything
+        if let Some(drawn) = self.deck.last() {
+            if seat != 0 && !self.hidden.contains_key(drawn) {
+                return Err(IllegalAction::UnknownCard(*drawn));
+            }
+        }
+
+        self.remove_from_hand(seat, id)?;
+        if let Some(drawn) = self.deck.pop() {
+            self.draw_into_hand(seat, drawn, None)?;
+        }
+
+        Ok(identity)
+    }
+
+    pub(crate) fn remove_from_hand(
+        &mut self,
+        seat: usize,
+        id: CardId,
+    ) -> Result<(), IllegalAction> {
+        if seat >= self.players() {
+            return Err(IllegalAction::NoSuchPlayer(seat));
+        }
+        // Nothing changes unless the card is really there
+        if !self.hand_ids(seat).any(|cid| cid == id) {
+            return Err(IllegalAction::NotInHand(id));
+        }
+        if seat == 0 {
+            self.hidden.remove(&id);
+            self.player_hand.remove(id);
+        } else {
+            self.team_hands[seat - 1].remove(id);
+        }
+
+        Ok(())
+    }
+
+    // We can't see our own cards, but anyone else's card needs to either be
+    // given to us or already known
+    pub(crate) fn draw_into_hand(
+        &mut self,
+        seat: usize,
+        id: CardId,
+        identity: Option<(Color, usize)>,
+    ) -> Result<(), IllegalAction> {
+        if seat >= self.players() {
+            return Err(IllegalAction::NoSuchPlayer(seat));
+        }
+        if seat == 0 {
+            self.player_hand.draw(id);
+        } else {
+            let (color, number) = identity
+                .or_else(|| self.hidden.remove(&id))
+                .ok_or(IllegalAction::UnknownCard(id))?;
+            self.team_hands[seat - 1].draw(id, color, number);
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn hand_ids(&self, seat: usize) -> Box<dyn Iterator<Item = CardId> + '_> {
+        if seat == 0 {
+            Box::new(self.player_hand.iter().map(|(id, _, _, _)| *id))
+        } else {
+            Box::new(
+                self.team_hands[seat - 1]
+                    .hand
+                    .iter()
+                    .map(|(id, _, _, _)| *id),
+            )
+        }
+    }
+
+    fn identity_in_seat(&self, seat: usize, index: usize) -> Result<(Color, usize), IllegalAction> {
+        if seat == 0 {
+            let id = self.player_hand[index].0;
+            self.hidden
+                .get(&id)
+                .copied()
+                .ok_or(IllegalAction::UnknownCard(id))
+        } else {
+            let (_, color, number, _) = self.team_hands[seat - 1].hand[index];
+            Ok((color, number))
+        }
+    }
+
+    fn cards_touched_by(&self, seat: usize, hint: Hint) -> Result<Vec<CardId>, IllegalAction> {
+        let mut touched = Vec::new();
+        for (index, id) in self.hand_ids(seat).enumerate() {
+            if hint.applies_to_card(self.identity_in_seat(seat, index)?) {
+                touched.push(id);
+            }
+        }
+
+        Ok(touched)
+    }
+
+    pub(crate) fn receive_hint(&mut self, seat: usize, hint: Hint, touched: &[CardId]) {
+        if seat == 0 {
+            let slots: Vec<_> = self
+                .player_hand
+                .iter()
+                .enumerate()
+                .filter(|(_, (id, _, _, _))| touched.contains(id))
+                .map(|(index, _)| index)
+                .collect();
+            self.player_hand.apply_hint(hint, &slots);
+        } else {
+            let hand = &mut self.team_hands[seat - 1];
+            let slots: Vec<_> = hand
+                .hand
+                .iter()
+                .enumerate()
+                .filter(|(_, (id, _, _, _))| touched.contains(id))
+                .map(|(index, _)| index)
+                .collect();
+            hand.apply_hint(hint, &slots);
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use crate::{
+    rules::MAX_HINTS, Action, Brain, BuildError, CardId, Color, Game, GameEvent, GameStatus,
+    IllegalAction, Outcome, Priorities,
+};
+
+pub struct Simulator {
+    seed: u64,
+    game: Game,
+    brains: Vec<Brain>,
+}
+
+pub struct GameRecord {
+    seed: u64,
+    players: usize,
+    deck: Vec<(CardId, Color, usize)>,
+    turns: Vec<(usize, Action, Outcome)>,
+    status: GameStatus,
+    clues_wasted: usize,
+}
+
+impl GameRecord {
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn players(&self) -> usize {
+        self.players
+    }
+
+    // Every card in the order it was drawn
+    pub fn deck(&self) -> &[(CardId, Color, usize)] {
+        &self.deck
+    }
+
+    // Who acted, what they did and what happened
+    pub fn turns(&self) -> &[(usize, Action, Outcome)] {
+        &self.turns
+    }
+
+    pub fn status(&self) -> GameStatus {
+        self.status
+    }
+
+    // Hints that didn't touch anything new, and 5s played when we couldn't get a hint back
+    pub fn clues_wasted(&self) -> usize {
+        self.clues_wasted
+    }
+
+    pub fn score(&self) -> usize {
+        match self.status {
+            GameStatus::InProgress => 0,
+            GameStatus::Won(score)
+            | GameStatus::LostToStrikes(score)
+            | GameStatus::EndedByDeck(score) => score,
+        }
+    }
+}
+
+impl Simulator {
+    pub fn new(players: usize, seed: u64) -> Result<Self, BuildError> {
+        Self::with_priorities(players, seed, Priorities::default())
+    }
+
+    // Every player thinks with the same `priorities`
+    pub fn with_priorities(
+        players: usize,
+        seed: u64,
+        priorities: Priorities,
+    ) -> Result<Self, BuildError> {
+        Ok(Self {
+            seed,
+            game: Game::new(players, seed)?,
+            brains: (0..players)
+                .map(|_| Brain::with_priorities(priorities))
+                .collect(),
+        })
+    }
+
+    pub fn run(mut self) -> Result<GameRecord, IllegalAction> {
+        let mut turns = Vec::new();
+        let mut clues_wasted = 0;
+        while self.game.status() == GameStatus::InProgress {
+            let seat = self.game.current_player();
+            let mut views: Vec<_> = (0..self.game.players())
+                .map(|s| self.game.view(s))
+                .collect();
+            let action = self.brains[seat].play(&views[seat])?;
+            let hints_full = self.game.hint_count() == MAX_HINTS;
+            let touched = self.game.touched_cards();
+            let drawn = self.game.next_card();
+            let outcome = self.game.apply(action)?;
+            log::debug!("Player {seat}: {action:?} -> {outcome:?}");
+
+            // Let everyone see what happened, keeping their views in step as they go
+            for (viewer, view) in views.iter_mut().enumerate() {
+                for event in self.events(viewer, seat, action, &outcome, drawn) {
+                    self.brains[viewer].observe(&event, view);
+                    view.record(&event)?;
+                }
+            }
+
+            match &outcome {
+                Outcome::Hinted(_, hinted) if hinted.iter().all(|id| touched.contains(id)) => {
+                    clues_wasted += 1
+                }
+                Outcome::Played(_, _, 5) if hints_full => clues_wasted += 1,
+                _ => {}
+            }
+            turns.push((seat, action, outcome));
+        }
+
+        Ok(GameRecord {
+            seed: self.seed,
+            players: self.game.players(),
+            deck: self.game.deck().to_vec(),
+            turns,
+            status: self.game.status(),
+            clues_wasted,
+        })
+    }
+
+    // What `viewer` saw happen when `actor` took their turn
+    fn events(
+        &self,
+        viewer: usize,
+        actor: usize,
+        action: Action,
+        outcome: &Outcome,
+        drawn: Option<(CardId, Color, usize)>,
+    ) -> Vec<GameEvent> {
+        let players = self.game.players();
+        let seat = |s: usize| (s + players - viewer) % players;
+
+        let mut events = vec![match (action, outcome.clone()) {
+            (_, Outcome::Played(id, c, n)) => GameEvent::Play(seat(actor), id, c, n),
+            (_, Outcome::Bombed(id, c, n)) => GameEvent::Bomb(seat(actor), id, c, n),
+            (_, Outcome::Discarded(id, c, n)) => GameEvent::Discard(seat(actor), id, c, n),
+            (Action::Hint(_, hint), Outcome::Hinted(target, touched)) => {
+                GameEvent::Hint(seat(actor), seat(target), hint, touched)
+            }
+            (_, Outcome::Hinted(_, _)) => unreachable!("only hints touch cards"),
+        }];
+        if !matches!(action, Action::Hint(_, _)) {
+            if let Some((id, c, n)) = drawn {
+                // Nobody gets to see what they drew themselves
+                let identity = (viewer != actor).then_some((c, n));
+                events.push(GameEvent::Draw(seat(actor), id, identity));
+            }
+        }
+        events.push(GameEvent::Turn(
+            self.game.turn(),
+            seat(self.game.current_player()),
+        ));
+
+        events
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{Action, CardId, Poll, Retired};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Step {
+    Note(String),
+    Question(String),
+    Answer(String),
+}
+
+// What a thought worked through while it was being polled
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Reasoning(Vec<Step>);
+
+impl Reasoning {
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    pub fn note(&mut self, text: impl Into<String>) {
+        self.0.push(Step::Note(text.into()));
+    }
+
+    pub fn question(&mut self, text: impl Into<String>) {
+        self.0.push(Step::Question(text.into()));
+    }
+
+    pub fn answer(&mut self, text: impl Into<String>) {
+        self.0.push(Step::Answer(text.into()));
+    }
+
+    pub fn steps(&self) -> &[Step] {
+        &self.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PolledThought {
+    pub thought: &'static str,
+    pub card: Option<CardId>,
+    pub steps: Vec<Step>,
+    pub result: Poll,
+}
+
+// Everything the Brain went through to decide on one turn's action
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DecisionTrace {
+    pub turn: usize,
+    pub retired: Vec<Retired>,
+    pub polled: Vec<PolledThought>,
+    // None of the thoughts came up with anything we could do
+    pub fallback: bool,
+    pub action: Action,
+}
+
+impl DecisionTrace {
+    #[cfg(all(feature = "serde", feature = "serde_json"))]
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+}
+
+impl std::fmt::Display for DecisionTrace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Turn {}", self.turn)?;
+        for retired in &self.retired {
+            writeln!(
+                f,
+                "  Stopped thinking about {} ({}): {:?}",
+                retired.card.0, retired.thought, retired.reason
+            )?;
+        }
+        for polled in &self.polled {
+            match polled.card {
+                Some(card) => writeln!(f, "  {} thought about {}", polled.thought, card.0)?,
+                None => writeln!(f, "  {} thought", polled.thought)?,
+            }
+            for step in &polled.steps {
+                match step {
+                    Step::Note(text) => writeln!(f, "    {text}")?,
+                    Step::Question(text) => writeln!(f, "    Q: {text}")?,
+                    Step::Answer(text) => writeln!(f, "    A: {text}")?,
+                }
+            }
+            writeln!(f, "    -> {:?}", polled.result)?;
+        }
+        if self.fallback {
+            writeln!(f, "  Nothing came of that, so falling back to a safe move")?;
+        }
+        write!(f, "  {:?}", self.action)
+    }
+}
@@ -0,0 +1,149 @@
+mod common;
+
+use analytical::{
+    CardId, Color, ColorFlags, GameEvent, GameState, Hint, NumberFlags, PlayerHand, TeammateHand,
+};
+use common::{hand, thoughts, unknown_hand};
+
+// Player 2 clues player 1's red 2 with nothing played. We're next after them,
+// and nobody else can have red 1, so our finesse slot is being bluffed.
+#[test]
+fn we_are_bluffed_after_the_clue_giver() {
+    let state = GameState::builder()
+        .player_hand(unknown_hand())
+        .teammate_hand(hand(
+            5,
+            &[
+                (Color::Red, 2),
+                (Color::Blue, 4),
+                (Color::Yellow, 4),
+                (Color::Green, 4),
+                (Color::Purple, 3),
+            ],
+        ))
+        .teammate_hand(hand(
+            10,
+            &[
+                (Color::Blue, 3),
+                (Color::Yellow, 3),
+                (Color::Green, 3),
+                (Color::Purple, 4),
+                (Color::Blue, 2),
+            ],
+        ))
+        .build()
+        .unwrap();
+
+    let clue = GameEvent::Hint(2, 1, Hint::Number(2), vec![CardId(5)]);
+    assert_eq!(thoughts(&state, clue), ["bluffed"]);
+}
+
+// Four players, where player 1 clues player 3's red 2 and player 2 is between them
+fn four_players(finesse_slot: (Color, usize)) -> GameState {
+    GameState::builder()
+        .player_hand(PlayerHand::new(
+            (0..4)
+                .map(|i| (CardId(i), ColorFlags::all(), NumberFlags::all(), false))
+                .collect(),
+        ))
+        .teammate_hand(hand(
+            4,
+            &[
+                (Color::Blue, 4),
+                (Color::Yellow, 4),
+                (Color::Green, 4),
+                (Color::Purple, 4),
+            ],
+        ))
+        .teammate_hand(hand(
+            8,
+            &[
+                finesse_slot,
+                (Color::Yellow, 3),
+                (Color::Green, 3),
+                (Color::Purple, 3),
+            ],
+        ))
+        .teammate_hand(hand(
+            12,
+            &[
+                (Color::Red, 2),
+                (Color::Blue, 3),
+                (Color::Yellow, 2),
+                (Color::Green, 2),
+            ],
+        ))
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn a_teammate_is_bluffed_with_a_card_that_does_not_connect() {
+    let clue = GameEvent::Hint(1, 3, Hint::Color(Color::Red), vec![CardId(12)]);
+    assert_eq!(thoughts(&four_players((Color::Blue, 1)), clue), ["bluff"]);
+}
+
+#[test]
+fn a_card_that_connects_is_a_finesse_instead() {
+    let clue = GameEvent::Hint(1, 3, Hint::Color(Color::Red), vec![CardId(12)]);
+    assert_eq!(thoughts(&four_players((Color::Red, 1)), clue), ["finesse"]);
+}
+
+// Player 2 already has red 1 clued, so the red 2 is a prompt on that rather
+// than a bluff on their blue 1
+#[test]
+fn a_prompt_is_not_a_bluff() {
+    let state = GameState::builder()
+        .player_hand(PlayerHand::new(
+            (0..4)
+                .map(|i| (CardId(i), ColorFlags::all(), NumberFlags::all(), false))
+                .collect(),
+        ))
+        .teammate_hand(hand(
+            4,
+            &[
+                (Color::Blue, 4),
+                (Color::Yellow, 4),
+                (Color::Green, 4),
+                (Color::Purple, 4),
+            ],
+        ))
+        .teammate_hand(TeammateHand::new(vec![
+            (CardId(8), Color::Blue, 1, false),
+            (CardId(9), Color::Red, 1, true),
+            (CardId(10), Color::Green, 3, false),
+            (CardId(11), Color::Purple, 3, false),
+        ]))
+        .teammate_hand(hand(
+            12,
+            &[
+                (Color::Red, 2),
+                (Color::Blue, 3),
+                (Color::Yellow, 2),
+                (Color::Green, 2),
+            ],
+        ))
+        .build()
+        .unwrap();
+
+    let clue = GameEvent::Hint(1, 3, Hint::Color(Color::Red), vec![CardId(12)]);
+    assert_eq!(thoughts(&state, clue), ["prompt"]);
+}
+
+// With red 1 to 4 played, a 5 clued off chop could be the red 5
+#[test]
+fn a_five_off_chop_that_could_be_played_is_a_play_clue() {
+    let state = GameState::builder()
+        .player_hand(unknown_hand())
+        .teammate_hand(common::fours(5))
+        .played(CardId(20), Color::Red, 1)
+        .played(CardId(21), Color::Red, 2)
+        .played(CardId(22), Color::Red, 3)
+        .played(CardId(23), Color::Red, 4)
+        .hint_count(7)
+        .build()
+        .unwrap();
+
+    let clue = GameEvent::Hint(1, 0, Hint::Number(5), vec![CardId(0)]);
+    assert_eq!(thoughts(&state, clue), ["play"]);
+}
@@ -0,0 +1,133 @@
+mod common;
+
+use analytical::{
+    BuildError, CardId, Color, GameState, GameStateBuilder, GameStatus, TeammateHand,
+};
+use common::{fours, unknown_hand};
+
+fn builder() -> GameStateBuilder {
+    GameState::builder()
+        .player_hand(unknown_hand())
+        .teammate_hand(fours(5))
+}
+
+#[test]
+fn a_valid_state_builds() {
+    let state = builder().hint_count(3).strikes(2).build().unwrap();
+    assert_eq!(state.players(), 2);
+    assert_eq!(state.hint_count(), 3);
+    assert_eq!(state.strikes(), 2);
+    assert_eq!(state.turns_left(), None);
+}
+
+#[test]
+fn player_counts_are_checked() {
+    assert_eq!(
+        GameState::builder()
+            .player_hand(unknown_hand())
+            .build()
+            .err(),
+        Some(BuildError::WrongPlayerCount(1))
+    );
+    let crowded = (0..5).fold(builder(), |b, i| b.teammate_hand(fours(10 + 5 * i)));
+    assert_eq!(crowded.build().err(), Some(BuildError::WrongPlayerCount(7)));
+    assert_eq!(
+        GameState::builder().teammate_hand(fours(5)).build().err(),
+        Some(BuildError::MissingPlayerHand)
+    );
+    assert_eq!(
+        builder().current_player(2).build().err(),
+        Some(BuildError::NoSuchPlayer(2))
+    );
+}
+
+#[test]
+fn hands_are_sized_for_the_player_count() {
+    // Four players only get four cards each
+    let four_players = builder()
+        .teammate_hand(TeammateHand::new(vec![(CardId(10), Color::Red, 1, false)]))
+        .teammate_hand(TeammateHand::new(vec![(CardId(11), Color::Red, 1, false)]));
+    assert_eq!(four_players.build().err(), Some(BuildError::HandTooBig(0)));
+
+    let big = TeammateHand::new(
+        (0..6)
+            .map(|i| (CardId(10 + i), Color::ALL[i % 5], 3, false))
+            .collect(),
+    );
+    assert_eq!(
+        GameState::builder()
+            .player_hand(unknown_hand())
+            .teammate_hand(big)
+            .build()
+            .err(),
+        Some(BuildError::HandTooBig(1))
+    );
+}
+
+#[test]
+fn every_card_is_only_in_one_place() {
+    assert_eq!(
+        builder().discarded(CardId(5), Color::Blue, 4).build().err(),
+        Some(BuildError::DuplicateCard(CardId(5)))
+    );
+    assert_eq!(
+        builder().deck(vec![CardId(0)]).build().err(),
+        Some(BuildError::DuplicateCard(CardId(0)))
+    );
+}
+
+#[test]
+fn there_are_only_so_many_copies() {
+    let result = builder()
+        .discarded(CardId(10), Color::Blue, 4)
+        .discarded(CardId(11), Color::Blue, 4)
+        .build();
+    assert_eq!(
+        result.err(),
+        Some(BuildError::TooManyCopies(Color::Blue, 4))
+    );
+
+    let result = builder().hidden(CardId(0), Color::Red, 6).build();
+    assert_eq!(result.err(), Some(BuildError::InvalidNumber(6)));
+}
+
+#[test]
+fn stacks_are_played_in_order() {
+    let result = builder()
+        .played(CardId(10), Color::Red, 1)
+        .played(CardId(11), Color::Red, 3)
+        .build();
+    assert_eq!(result.err(), Some(BuildError::BrokenStack(Color::Red)));
+}
+
+#[test]
+fn hints_and_strikes_are_bounded() {
+    assert_eq!(
+        builder().hint_count(9).build().err(),
+        Some(BuildError::TooManyHints(9))
+    );
+    assert_eq!(
+        builder().strikes(3).build().err(),
+        Some(BuildError::TooManyStrikes(3))
+    );
+}
+
+#[test]
+fn final_round_can_be_built() {
+    let state = builder().turns_left(1).build().unwrap();
+    assert_eq!(state.turns_left(), Some(1));
+    assert_eq!(state.status(), GameStatus::InProgress);
+    assert_eq!(
+        builder().turns_left(0).build().unwrap().status(),
+        GameStatus::EndedByDeck(0)
+    );
+
+    assert_eq!(
+        builder().turns_left(3).build().err(),
+        Some(BuildError::TooManyTurnsLeft(3))
+    );
+    assert_eq!(
+        builder().deck(vec![CardId(10)]).turns_left(1).build().err(),
+        Some(BuildError::FinalRoundWithCardsLeft)
+    );
+}
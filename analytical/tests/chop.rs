@@ -0,0 +1,66 @@
+mod common;
+
+use analytical::{
+    CardId, Color, ColorFlags, GameEvent, GameState, IllegalAction, NumberFlags, PlayerHand,
+    TeammateHand,
+};
+use common::unknown_hand;
+
+// Slot 0 is the newest card, so chop is the highest untouched slot
+#[test]
+fn chop_is_the_oldest_untouched_card() {
+    let mut hand = unknown_hand();
+    assert_eq!(hand.get_chop().map(|(id, _)| id), Some(CardId(4)));
+
+    let touched = |i| (CardId(i), ColorFlags::all(), NumberFlags::all(), true);
+    let untouched = |i| (CardId(i), ColorFlags::all(), NumberFlags::all(), false);
+    hand = PlayerHand::new(vec![
+        untouched(0),
+        untouched(1),
+        touched(2),
+        untouched(3),
+        touched(4),
+    ]);
+    assert_eq!(hand.get_chop().map(|(id, _)| id), Some(CardId(3)));
+
+    hand = PlayerHand::new(vec![touched(0), touched(1)]);
+    assert!(hand.get_chop().is_none());
+}
+
+#[test]
+fn teammate_chop_is_the_oldest_untouched_card() {
+    let state = GameState::builder()
+        .player_hand(unknown_hand())
+        .teammate_hand(TeammateHand::new(vec![
+            (CardId(5), Color::Blue, 1, false),
+            (CardId(6), Color::Yellow, 2, false),
+            (CardId(7), Color::Green, 3, false),
+            (CardId(8), Color::Red, 4, true),
+            (CardId(9), Color::Purple, 5, true),
+        ]))
+        .build()
+        .unwrap();
+
+    let chop = state.get_chop_for_teammate(0).unwrap();
+    assert_eq!(chop.to_string(), "g3");
+}
+
+// Card 10 is still in the deck, so we can't have played it
+#[test]
+fn removing_a_missing_card_changes_nothing() {
+    let mut state = GameState::builder()
+        .player_hand(unknown_hand())
+        .teammate_hand(common::fours(5))
+        .deck(vec![CardId(10)])
+        .hidden(CardId(10), Color::Red, 1)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        state.record(&GameEvent::Play(0, CardId(10), Color::Red, 1)),
+        Err(IllegalAction::NotInHand(CardId(10)))
+    );
+    // We still know what it is when the teammate draws it
+    state.record(&GameEvent::Draw(1, CardId(10), None)).unwrap();
+    assert_eq!(CardId(10).resolve(&state).to_string(), "r1");
+}
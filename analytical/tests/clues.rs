@@ -0,0 +1,183 @@
+mod common;
+
+use analytical::{
+    Brain, CardId, Color, ColorFlags, Focus, GameEvent, GameState, Hint, IllegalAction,
+    NumberFlags, PlayerHand, Step, TeammateHand,
+};
+use common::{fours, state, unknown_hand};
+
+// What we make of the teammate cluing `hint` onto our `touched` cards
+fn thoughts_about(state: &GameState, hint: Hint, touched: &[usize]) -> Vec<&'static str> {
+    let event = GameEvent::Hint(1, 0, hint, touched.iter().map(|i| CardId(*i)).collect());
+    common::thoughts(state, event)
+}
+
+#[test]
+fn focus_is_chop_when_it_is_touched() {
+    let hand = unknown_hand();
+    assert_eq!(
+        hand.determine_focus_for_hint(&[1, 4]),
+        Ok(Focus::Chop(CardId(4), 4))
+    );
+}
+
+// Even when it's the only card touched, it's a chop focus rather than a new card
+#[test]
+fn focus_is_chop_when_only_chop_is_touched() {
+    let hand = unknown_hand();
+    assert_eq!(
+        hand.determine_focus_for_hint(&[4]),
+        Ok(Focus::Chop(CardId(4), 4))
+    );
+}
+
+#[test]
+fn focus_is_the_only_new_card() {
+    let hand = PlayerHand::new(vec![
+        (CardId(0), ColorFlags::Red, NumberFlags::all(), true),
+        (CardId(1), ColorFlags::all(), NumberFlags::all(), false),
+        (CardId(2), ColorFlags::all(), NumberFlags::all(), false),
+    ]);
+    assert_eq!(
+        hand.determine_focus_for_hint(&[0, 1]),
+        Ok(Focus::NewCard(CardId(1), 1))
+    );
+}
+
+#[test]
+fn focus_is_the_leftmost_new_card() {
+    let hand = unknown_hand();
+    assert_eq!(
+        hand.determine_focus_for_hint(&[1, 2]),
+        Ok(Focus::LeftMost(CardId(1), 1))
+    );
+}
+
+#[test]
+fn focus_of_a_retouch_is_the_leftmost_card() {
+    let touched = |i| (CardId(i), ColorFlags::all(), NumberFlags::all(), true);
+    let hand = PlayerHand::new(vec![touched(0), touched(1), touched(2)]);
+    assert_eq!(
+        hand.determine_focus_for_hint(&[1, 2]),
+        Ok(Focus::LeftMost(CardId(1), 1))
+    );
+    assert_eq!(
+        hand.determine_focus_for_hint(&[]),
+        Err(IllegalAction::EmptyHint)
+    );
+}
+
+#[test]
+fn focus_on_a_teammate_follows_the_same_rules() {
+    let state = GameState::builder()
+        .player_hand(unknown_hand())
+        .teammate_hand(TeammateHand::new(vec![
+            (CardId(5), Color::Red, 1, false),
+            (CardId(6), Color::Red, 2, false),
+            (CardId(7), Color::Blue, 1, false),
+            (CardId(8), Color::Green, 3, false),
+            (CardId(9), Color::Red, 4, false),
+        ]))
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        state.get_focus_for_hint(0, Hint::Color(Color::Red)),
+        Ok(Focus::Chop(CardId(9), 4))
+    );
+    assert_eq!(
+        state.get_focus_for_hint(0, Hint::Number(1)),
+        Ok(Focus::LeftMost(CardId(5), 0))
+    );
+    assert_eq!(
+        state.get_focus_for_hint(0, Hint::Number(2)),
+        Ok(Focus::NewCard(CardId(6), 1))
+    );
+    assert_eq!(
+        state.get_focus_for_hint(0, Hint::Number(5)),
+        Err(IllegalAction::EmptyHint)
+    );
+}
+
+// The server told us it touched a card the hint can't have touched
+#[test]
+fn a_hint_that_touches_nothing_is_ignored() {
+    let event = GameEvent::Hint(0, 1, Hint::Number(1), vec![CardId(5)]);
+    assert!(common::thoughts(&state(7), event).is_empty());
+}
+
+#[test]
+fn a_clue_that_could_be_playable_is_a_play_clue() {
+    assert_eq!(thoughts_about(&state(7), Hint::Number(1), &[0]), ["play"]);
+}
+
+#[test]
+fn fives_are_saved_on_chop_and_stalled_off_it() {
+    assert_eq!(
+        thoughts_about(&state(7), Hint::Number(5), &[4]),
+        ["five save"]
+    );
+    assert_eq!(
+        thoughts_about(&state(7), Hint::Number(5), &[0]),
+        ["five stall"]
+    );
+}
+
+#[test]
+fn twos_are_saved_on_chop() {
+    assert_eq!(
+        thoughts_about(&state(7), Hint::Number(2), &[4]),
+        ["two save"]
+    );
+}
+
+#[test]
+fn critical_cards_are_saved_on_chop() {
+    let state = GameState::builder()
+        .player_hand(unknown_hand())
+        .teammate_hand(fours(5))
+        .discarded(CardId(10), Color::Red, 3)
+        .discarded(CardId(11), Color::Blue, 3)
+        .discarded(CardId(12), Color::Green, 3)
+        .discarded(CardId(13), Color::Yellow, 3)
+        .discarded(CardId(14), Color::Purple, 3)
+        .build()
+        .unwrap();
+    assert_eq!(thoughts_about(&state, Hint::Number(3), &[4]), ["save"]);
+}
+
+// Nothing's been played, and nobody holds anything that could lead up to it,
+// so it can't be a play clue. Playing it would bomb.
+#[test]
+fn an_unreachable_card_off_chop_is_not_played() {
+    assert_eq!(thoughts_about(&state(7), Hint::Number(4), &[0]), ["save"]);
+    assert_eq!(thoughts_about(&state(7), Hint::Number(3), &[1]), ["save"]);
+}
+
+// The clue giver has to agree, and leave a critical card that's off chop alone
+#[test]
+fn critical_cards_off_chop_are_not_clued() {
+    let state = GameState::builder()
+        .player_hand(unknown_hand())
+        .teammate_hand(TeammateHand::new(vec![
+            (CardId(5), Color::Red, 4, false),
+            (CardId(6), Color::Yellow, 4, false),
+            (CardId(7), Color::Green, 4, false),
+            (CardId(8), Color::Blue, 4, false),
+            (CardId(9), Color::Purple, 4, false),
+        ]))
+        .discarded(CardId(10), Color::Red, 4)
+        .hint_count(7)
+        .build()
+        .unwrap();
+
+    let trace = Brain::new().play_with_trace(&state).unwrap();
+    let early_game = trace
+        .polled
+        .iter()
+        .find(|p| p.thought == "early game")
+        .unwrap();
+    assert!(early_game.steps.contains(&Step::Note(
+        "However, it's not on their chop, so it's safe for now...".into()
+    )));
+}
@@ -0,0 +1,70 @@
+// Each test binary only uses some of these
+#![allow(dead_code)]
+
+use analytical::{
+    Brain, CardId, ClueInterpreter, Color, ColorFlags, GameEvent, GameState, NumberFlags,
+    PlayerHand, TeammateHand, ThoughtGenerator,
+};
+
+// Cards 0 to 4, none of them touched, so we know nothing about them
+pub fn unknown_hand() -> PlayerHand {
+    PlayerHand::new(
+        (0..5)
+            .map(|i| (CardId(i), ColorFlags::all(), NumberFlags::all(), false))
+            .collect(),
+    )
+}
+
+// One untouched 4 of each color, numbered from `first`
+pub fn fours(first: usize) -> TeammateHand {
+    TeammateHand::new(
+        Color::ALL
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (CardId(first + i), *c, 4, false))
+            .collect(),
+    )
+}
+
+// Untouched cards numbered from `first`, newest first
+pub fn hand(first: usize, cards: &[(Color, usize)]) -> TeammateHand {
+    TeammateHand::new(
+        cards
+            .iter()
+            .enumerate()
+            .map(|(i, (c, n))| (CardId(first + i), *c, *n, false))
+            .collect(),
+    )
+}
+
+// Two players: our `unknown_hand`, and a teammate holding cards 5 to 9 as `fours`
+pub fn state(hint_count: usize) -> GameState {
+    GameState::builder()
+        .player_hand(unknown_hand())
+        .teammate_hand(fours(5))
+        .hint_count(hint_count)
+        .build()
+        .unwrap()
+}
+
+// The names of the thoughts we'd have about `event`
+pub fn thoughts(state: &GameState, event: GameEvent) -> Vec<&'static str> {
+    ClueInterpreter::new()
+        .observe(&event, state)
+        .iter()
+        .map(|t| t.name())
+        .collect()
+}
+
+// Shows each event to `brain` and then records it, the same way the simulator does
+pub fn replay(brain: &mut Brain, state: &mut GameState, events: &[GameEvent]) {
+    for event in events {
+        brain.observe(event, state);
+        state.record(event).unwrap();
+    }
+}
+
+// Someone gives us `clue`, and then it's our turn
+pub fn clue(brain: &mut Brain, state: &mut GameState, clue: GameEvent) {
+    replay(brain, state, &[clue, GameEvent::Turn(1, 0)]);
+}
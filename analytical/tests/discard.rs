@@ -0,0 +1,149 @@
+mod common;
+
+use analytical::{
+    Action, Brain, CardId, Color, ColorFlags, GameEvent, GameState, Hint, NumberFlags, Outcome,
+    PlayerHand, Poll, TeammateHand,
+};
+
+// Our second card is known to be red 1, which has already been played
+fn state() -> GameState {
+    GameState::builder()
+        .player_hand(PlayerHand::new(
+            (0..5)
+                .map(|i| match i {
+                    1 => (CardId(i), ColorFlags::Red, NumberFlags::One, true),
+                    _ => (CardId(i), ColorFlags::all(), NumberFlags::all(), false),
+                })
+                .collect(),
+        ))
+        .teammate_hand(common::fours(5))
+        .played(CardId(10), Color::Red, 1)
+        .hint_count(0)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn known_trash_is_discarded_before_chop() {
+    let mut brain = Brain::new();
+    let trace = brain.play_with_trace(&state()).unwrap();
+
+    assert!(trace.fallback);
+    assert_eq!(trace.action, Action::Discard(CardId(1)));
+}
+
+// Our hand from newest to oldest, with which cards are touched
+fn our_hand(cards: [(NumberFlags, bool); 5], hint_count: usize) -> GameState {
+    GameState::builder()
+        .player_hand(PlayerHand::new(
+            cards
+                .iter()
+                .enumerate()
+                .map(|(i, (number, touched))| (CardId(i), ColorFlags::all(), *number, *touched))
+                .collect(),
+        ))
+        .teammate_hand(common::fours(5))
+        .hint_count(hint_count)
+        .build()
+        .unwrap()
+}
+
+// Every card we have is a 5, so we'd rather give a clue than lose one
+#[test]
+fn critical_cards_are_kept_while_we_can_give_a_clue() {
+    let state = our_hand([(NumberFlags::Five, false); 5], 3);
+    let mut brain = Brain::new();
+    let trace = brain.play_with_trace(&state).unwrap();
+
+    let discard = trace
+        .polled
+        .iter()
+        .find(|p| p.thought == "discard")
+        .expect("the discard thought was polled");
+    assert_eq!(discard.result, Poll::Pending(None));
+    assert!(trace.fallback);
+    assert!(matches!(trace.action, Action::Hint(_, _)));
+}
+
+// Our chop is a 5, so we let go of the untouched 1 instead: there are more
+// copies of it left than of anything our unknown cards could be
+#[test]
+fn a_critical_chop_is_kept_for_the_card_with_most_copies() {
+    let state = our_hand(
+        [
+            (NumberFlags::all(), false),
+            (NumberFlags::One, true),
+            (NumberFlags::One, false),
+            (NumberFlags::all(), false),
+            (NumberFlags::Five, false),
+        ],
+        0,
+    );
+    let mut brain = Brain::new();
+    assert_eq!(brain.play(&state).unwrap(), Action::Discard(CardId(2)));
+}
+
+// With everything touched there's no chop, and the same goes for touched cards
+#[test]
+fn without_a_chop_the_card_with_most_copies_is_discarded() {
+    let state = our_hand(
+        [
+            (NumberFlags::all(), true),
+            (NumberFlags::all(), true),
+            (NumberFlags::One, true),
+            (NumberFlags::all(), true),
+            (NumberFlags::Five, true),
+        ],
+        0,
+    );
+    let mut brain = Brain::new();
+    assert_eq!(brain.play(&state).unwrap(), Action::Discard(CardId(2)));
+}
+
+// With every hint available we can't discard. Player 1's newest card is a blue 2,
+// and player 2 has blue 1 clued, so a 2 would be read as a prompt on a card that
+// isn't playable yet.
+fn full_hints() -> GameState {
+    GameState::builder()
+        .player_hand(common::unknown_hand())
+        .teammate_hand(common::hand(
+            5,
+            &[
+                (Color::Blue, 2),
+                (Color::Yellow, 3),
+                (Color::Green, 3),
+                (Color::Purple, 3),
+                (Color::Yellow, 4),
+            ],
+        ))
+        .teammate_hand(TeammateHand::new(vec![
+            (CardId(10), Color::Green, 4, false),
+            (CardId(11), Color::Blue, 1, true),
+            (CardId(12), Color::Purple, 4, false),
+            (CardId(13), Color::Red, 3, false),
+            (CardId(14), Color::Red, 4, false),
+        ]))
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn fallback_hints_are_not_read_as_play_clues() {
+    let state = full_hints();
+    let mut brain = Brain::new();
+    let trace = brain.play_with_trace(&state).unwrap();
+
+    assert!(trace.fallback);
+    let Action::Hint(player, hint) = trace.action else {
+        panic!("expected a hint, got {:?}", trace.action);
+    };
+    assert_ne!(hint, Hint::Number(2));
+    assert_ne!(hint, Hint::Color(Color::Blue));
+
+    // Nobody has anything to play because of it
+    let Ok(Outcome::Hinted(_, touched)) = full_hints().apply(trace.action) else {
+        panic!("{:?} couldn't be given", trace.action);
+    };
+    let event = GameEvent::Hint(0, player + 1, hint, touched);
+    assert!(common::thoughts(&state, event).is_empty());
+}
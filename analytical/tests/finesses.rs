@@ -0,0 +1,142 @@
+mod common;
+
+use analytical::{
+    Action, Brain, CardId, Color, ColorFlags, GameEvent, GameState, Hint, NumberFlags, PlayerHand,
+    TeammateHand,
+};
+use common::{clue, hand, replay, thoughts, unknown_hand};
+
+// Player 1 clues player 2's red 2, and nobody else has red 1
+#[test]
+fn we_are_finessed_by_a_clue_to_a_teammate() {
+    let state = GameState::builder()
+        .player_hand(unknown_hand())
+        .teammate_hand(hand(
+            5,
+            &[
+                (Color::Blue, 3),
+                (Color::Yellow, 3),
+                (Color::Green, 3),
+                (Color::Purple, 3),
+                (Color::Blue, 4),
+            ],
+        ))
+        .teammate_hand(hand(
+            10,
+            &[
+                (Color::Red, 2),
+                (Color::Yellow, 4),
+                (Color::Green, 4),
+                (Color::Purple, 4),
+                (Color::Blue, 5),
+            ],
+        ))
+        .build()
+        .unwrap();
+
+    let clue = GameEvent::Hint(1, 2, Hint::Color(Color::Red), vec![CardId(10)]);
+    assert_eq!(thoughts(&state, clue), ["finessed"]);
+}
+
+// Player 2 clues player 1's red 3. Player 1 already has red 1 clued, so our
+// finesse slot has to be the red 2, but only once player 1 has played their red 1.
+// They go after us, so we have to wait a turn.
+#[test]
+fn finessed_cards_wait_for_the_cards_before_them() {
+    let mut state = GameState::builder()
+        .player_hand(unknown_hand())
+        .teammate_hand(TeammateHand::new(vec![
+            (CardId(5), Color::Red, 3, false),
+            (CardId(6), Color::Red, 1, true),
+            (CardId(7), Color::Blue, 4, false),
+            (CardId(8), Color::Yellow, 4, false),
+            (CardId(9), Color::Green, 4, false),
+        ]))
+        .teammate_hand(hand(
+            10,
+            &[
+                (Color::Blue, 3),
+                (Color::Yellow, 3),
+                (Color::Green, 3),
+                (Color::Purple, 3),
+                (Color::Purple, 4),
+            ],
+        ))
+        .hint_count(1)
+        .current_player(2)
+        .build()
+        .unwrap();
+    let mut brain = Brain::new();
+
+    let red = GameEvent::Hint(2, 1, Hint::Color(Color::Red), vec![CardId(5), CardId(6)]);
+    clue(&mut brain, &mut state, red);
+    assert_ne!(brain.play(&state).unwrap(), Action::Play(CardId(0)));
+
+    replay(
+        &mut brain,
+        &mut state,
+        &[
+            GameEvent::Play(1, CardId(6), Color::Red, 1),
+            GameEvent::Turn(2, 2),
+            GameEvent::Turn(3, 0),
+        ],
+    );
+    assert_eq!(brain.play(&state).unwrap(), Action::Play(CardId(0)));
+}
+
+// Four players, player 1 clues a 2 in our hand
+fn clued_two(finesse_slots: [(Color, usize); 2]) -> Vec<&'static str> {
+    let state = GameState::builder()
+        .player_hand(PlayerHand::new(
+            (0..4)
+                .map(|i| (CardId(i), ColorFlags::all(), NumberFlags::all(), false))
+                .collect(),
+        ))
+        .teammate_hand(hand(
+            4,
+            &[
+                (Color::Blue, 4),
+                (Color::Yellow, 4),
+                (Color::Green, 4),
+                (Color::Purple, 4),
+            ],
+        ))
+        .teammate_hand(hand(
+            8,
+            &[
+                finesse_slots[0],
+                (Color::Yellow, 3),
+                (Color::Green, 3),
+                (Color::Purple, 3),
+            ],
+        ))
+        .teammate_hand(hand(
+            12,
+            &[
+                finesse_slots[1],
+                (Color::Blue, 3),
+                (Color::Yellow, 5),
+                (Color::Green, 5),
+            ],
+        ))
+        .build()
+        .unwrap();
+
+    let clue = GameEvent::Hint(1, 0, Hint::Number(2), vec![CardId(0)]);
+    thoughts(&state, clue)
+}
+
+// Only red 1 is on a finesse slot, so our 2 can only be red and we wait for it
+#[test]
+fn our_card_connects_through_the_only_finesse_that_fits() {
+    assert_eq!(
+        clued_two([(Color::Red, 1), (Color::Purple, 5)]),
+        ["finesse"]
+    );
+}
+
+// Red 1 and blue 1 could each lead to our 2, so we can't tell which it is
+#[test]
+fn an_ambiguous_finesse_is_not_acted_on() {
+    assert_eq!(clued_two([(Color::Red, 1), (Color::Blue, 1)]), ["save"]);
+}
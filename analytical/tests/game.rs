@@ -0,0 +1,44 @@
+use analytical::{CardId, Game};
+
+const PLAYERS: usize = 3;
+const HAND: usize = 5;
+
+// Seat `s` is dealt cards `s * HAND` up to `(s + 1) * HAND`
+fn dealt_to(seat: usize) -> impl Iterator<Item = CardId> {
+    (seat * HAND..(seat + 1) * HAND).map(CardId)
+}
+
+#[test]
+fn views_hide_our_own_cards() {
+    let game = Game::new(PLAYERS, 7).unwrap();
+
+    for seat in 0..PLAYERS {
+        let view = game.view(seat);
+        for id in dealt_to(seat) {
+            // Nothing has been ruled out, so it could be anything
+            assert_eq!(
+                id.resolve(&view).to_string(),
+                "bygrp12345",
+                "seat {seat}, {id:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn views_show_everyone_elses_cards() {
+    let game = Game::new(PLAYERS, 7).unwrap();
+
+    for seat in 0..PLAYERS {
+        let view = game.view(seat);
+        for other in (0..PLAYERS).filter(|other| *other != seat) {
+            for id in dealt_to(other) {
+                let (_, color, number) = game.deck()[id.0];
+                assert_eq!(id.resolve(&view).to_string(), format!("{color}{number}"));
+            }
+        }
+        // Or what's still to come
+        let next = game.next_card().unwrap().0;
+        assert_eq!(next.resolve(&view).to_string(), "*Not Drawn*");
+    }
+}
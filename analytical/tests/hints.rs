@@ -0,0 +1,107 @@
+mod common;
+
+use analytical::{
+    Action, CardId, Color, ColorFlags, Game, GameEvent, GameState, Hint, NumberFlags, TeammateHand,
+};
+use common::unknown_hand;
+
+#[test]
+fn hints_narrow_touched_and_untouched_cards() {
+    let mut hand = unknown_hand();
+
+    hand.apply_hint(Hint::Color(Color::Red), &[0, 2]);
+    assert_eq!(
+        hand[0],
+        (CardId(0), ColorFlags::Red, NumberFlags::all(), true)
+    );
+    assert_eq!(
+        hand[1],
+        (
+            CardId(1),
+            ColorFlags::all() - ColorFlags::Red,
+            NumberFlags::all(),
+            false
+        )
+    );
+
+    hand.apply_hint(Hint::Number(1), &[0]);
+    assert_eq!(
+        hand[0],
+        (CardId(0), ColorFlags::Red, NumberFlags::One, true)
+    );
+    // Still touched from the first hint
+    assert_eq!(
+        hand[2],
+        (
+            CardId(2),
+            ColorFlags::Red,
+            NumberFlags::all() - NumberFlags::One,
+            true
+        )
+    );
+    assert_eq!(
+        hand[1],
+        (
+            CardId(1),
+            ColorFlags::all() - ColorFlags::Red,
+            NumberFlags::all() - NumberFlags::One,
+            false
+        )
+    );
+}
+
+// Their view of their own hand is exactly what we've worked out they know
+#[test]
+fn empathy_matches_what_the_teammate_sees() {
+    let mut game = Game::new(2, 3).unwrap();
+    let (id, color, _) = game.deck()[5];
+    game.apply(Action::Hint(0, Hint::Color(color))).unwrap();
+
+    let view = game.view(1);
+    assert_eq!(id.resolve(&view).to_string(), format!("{color}12345"));
+    for (other, c, _) in &game.deck()[6..10] {
+        if *c != color {
+            let colors = ColorFlags::all() - color.into();
+            assert_eq!(other.resolve(&view).to_string(), format!("{colors}12345"));
+        }
+    }
+}
+
+#[test]
+fn teammates_know_a_card_is_playable_once_its_pinned_down() {
+    let mut state = GameState::builder()
+        .player_hand(unknown_hand())
+        .teammate_hand(TeammateHand::new(vec![
+            (CardId(5), Color::Blue, 4, false),
+            (CardId(6), Color::Red, 1, false),
+            (CardId(7), Color::Green, 1, false),
+            (CardId(8), Color::Red, 3, false),
+            (CardId(9), Color::Yellow, 2, false),
+        ]))
+        .build()
+        .unwrap();
+    assert!(!state.teammate_knows_playable(0, 1));
+
+    state
+        .record(&GameEvent::Hint(
+            0,
+            1,
+            Hint::Color(Color::Red),
+            vec![CardId(6), CardId(8)],
+        ))
+        .unwrap();
+    assert!(!state.teammate_knows_playable(0, 1));
+
+    // Any 1 is playable with nothing on the stacks
+    state
+        .record(&GameEvent::Hint(
+            0,
+            1,
+            Hint::Number(1),
+            vec![CardId(6), CardId(7)],
+        ))
+        .unwrap();
+    assert!(state.teammate_knows_playable(0, 1));
+    assert!(state.teammate_knows_playable(0, 2));
+    assert!(!state.teammate_knows_playable(0, 3));
+}
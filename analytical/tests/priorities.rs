@@ -0,0 +1,32 @@
+use analytical::Priorities;
+
+#[test]
+fn missing_priorities_keep_their_defaults() {
+    let priorities = Priorities::from_json(r#"{"play": 3, "discard": 2}"#).unwrap();
+    assert_eq!(
+        priorities,
+        Priorities {
+            play: 3,
+            discard: 2,
+            ..Priorities::default()
+        }
+    );
+    assert_eq!(priorities.early_game, 10);
+}
+
+#[test]
+fn an_empty_config_is_the_default() {
+    assert_eq!(Priorities::from_json("{}").unwrap(), Priorities::default());
+}
+
+#[test]
+fn unknown_priorities_are_rejected() {
+    assert!(Priorities::from_json(r#"{"early_gmae": 1}"#).is_err());
+}
+
+#[test]
+fn invalid_priorities_are_rejected() {
+    assert!(Priorities::from_json(r#"{"play": -1}"#).is_err());
+    assert!(Priorities::from_json(r#"{"play": "soon"}"#).is_err());
+    assert!(Priorities::from_json("play = 1").is_err());
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use analytical::{
+    Action, Brain, CardId, Color, ColorFlags, GameEvent, GameState, Hint, NumberFlags, PlayerHand,
+    TeammateHand,
+};
+
+// We already hold a clued red card when our teammate clues a 2 in our hand.
+// The red card has to be red 1, and once it's played the 2 is red 2.
+#[test]
+fn a_self_prompt_ends_with_the_clued_card() {
+    let mut state = GameState::builder()
+        .player_hand(PlayerHand::new(
+            (0..5)
+                .map(|i| {
+                    if i == 2 {
+                        (CardId(i), ColorFlags::Red, NumberFlags::all(), true)
+                    } else {
+                        (CardId(i), ColorFlags::all(), NumberFlags::all(), false)
+                    }
+                })
+                .collect(),
+        ))
+        .teammate_hand(TeammateHand::new(vec![
+            (CardId(5), Color::Blue, 4, false),
+            (CardId(6), Color::Yellow, 4, false),
+            (CardId(7), Color::Green, 4, false),
+            (CardId(8), Color::Purple, 4, false),
+            (CardId(9), Color::Blue, 3, false),
+        ]))
+        .deck(vec![CardId(10), CardId(11)])
+        .hint_count(1)
+        .current_player(1)
+        .build()
+        .unwrap();
+    let mut brain = Brain::new();
+
+    let two = GameEvent::Hint(1, 0, Hint::Number(2), vec![CardId(0)]);
+    common::clue(&mut brain, &mut state, two);
+    assert_eq!(brain.play(&state).unwrap(), Action::Play(CardId(2)));
+
+    common::replay(
+        &mut brain,
+        &mut state,
+        &[
+            GameEvent::Play(0, CardId(2), Color::Red, 1),
+            GameEvent::Draw(0, CardId(10), None),
+            GameEvent::Turn(2, 1),
+            GameEvent::Discard(1, CardId(9), Color::Blue, 3),
+            GameEvent::Draw(1, CardId(11), Some((Color::Red, 4))),
+            GameEvent::Turn(3, 0),
+        ],
+    );
+    assert_eq!(brain.play(&state).unwrap(), Action::Play(CardId(0)));
+}
@@ -0,0 +1,110 @@
+use std::str::FromStr;
+
+use analytical::protocol::*;
+
+// Frames as hanab.live sends them
+const SERVER_FRAMES: &[&str] = &[
+    r#"welcome {"userID":1234,"username":"analytical-bot","totalGames":12,"muted":false,"firstTimeUser":false,"settings":{},"friends":[],"atOngoingTable":false,"randomTableName":"clever fox","shuttingDown":false,"datetimeShutdownInit":"0001-01-01T00:00:00Z","maintenanceMode":false}"#,
+    r#"warning {"warning":"You cannot join a table that has already started."}"#,
+    r#"error {"error":"That table does not exist."}"#,
+    r#"user {"userID":55,"name":"alice","status":1,"tableID":0,"hyphenated":false,"inactive":false}"#,
+    r#"userList [{"userID":55,"name":"alice","status":0,"tableID":0,"hyphenated":false,"inactive":false}]"#,
+    r#"userLeft {"userID":55}"#,
+    r#"tableList [{"id":7,"name":"alice's game","passwordProtected":false,"joined":false,"numPlayers":1,"owned":false,"running":false,"variant":"No Variant","timed":false,"timeBase":0,"timePerTurn":0,"sharedReplay":false,"progress":0,"players":["alice"],"spectators":[]}]"#,
+    r#"table {"id":7,"name":"alice's game","passwordProtected":false,"joined":true,"numPlayers":2,"owned":false,"running":false,"variant":"No Variant","timed":false,"timeBase":0,"timePerTurn":0,"sharedReplay":false,"progress":0,"players":["alice","analytical-bot"],"spectators":[]}"#,
+    r#"tableGone {"tableID":7}"#,
+    r#"tableStart {"tableID":7,"replay":false}"#,
+    r#"joined {"tableID":7}"#,
+    r#"left {}"#,
+    r#"chat {"msg":"/join","who":"alice","discord":false,"server":false,"datetime":"2024-05-01T12:00:00Z","room":"","recipient":"analytical-bot"}"#,
+    r#"init {"tableID":7,"playerNames":["alice","analytical-bot"],"ourPlayerIndex":1,"spectating":false,"shadowing":false,"replay":false,"databaseID":-1,"hasCustomSeed":false,"seed":"p2v0s1","datetimeStarted":"2024-05-01T12:00:00Z","datetimeFinished":"0001-01-01T00:00:00Z","options":{"numPlayers":2,"startingPlayer":0,"variantName":"No Variant","timed":false,"timeBase":0,"timePerTurn":0,"speedrun":false,"cardCycle":false,"deckPlays":false,"emptyClues":false,"oneExtraCard":false,"oneLessCard":false,"allOrNothing":false,"detrimentalCharacters":false},"characterAssignments":[],"characterMetadata":[],"sharedReplay":false,"paused":false,"pausePlayerIndex":-1,"pauseQueued":false}"#,
+    r#"gameActionList {"tableID":7,"list":[{"type":"draw","playerIndex":0,"order":0,"suitIndex":3,"rank":1},{"type":"draw","playerIndex":1,"order":5,"suitIndex":-1,"rank":-1},{"type":"status","clues":8,"score":0,"maxScore":25},{"type":"turn","num":0,"currentPlayerIndex":0}]}"#,
+    r#"gameAction {"tableID":7,"action":{"type":"clue","clue":{"type":0,"value":3},"giver":0,"list":[5,7],"target":1,"turn":0}}"#,
+    r#"gameAction {"tableID":7,"action":{"type":"play","playerIndex":1,"order":5,"suitIndex":3,"rank":1}}"#,
+    r#"gameAction {"tableID":7,"action":{"type":"discard","playerIndex":0,"order":2,"suitIndex":1,"rank":4,"failed":true}}"#,
+    r#"gameAction {"tableID":7,"action":{"type":"strike","num":1,"order":2,"turn":3}}"#,
+    r#"gameAction {"tableID":7,"action":{"type":"gameOver","endCondition":1,"playerIndex":0,"votes":null}}"#,
+    r#"gameAction {"tableID":7,"action":{"type":"cardIdentities","cardIdentities":[]}}"#,
+    r#"connected {"tableID":7,"list":[true,true]}"#,
+    r#"gameOver {"tableID":7}"#,
+];
+
+const CLIENT_FRAMES: &[&str] = &[
+    r#"tableCreate {"name":"bots only","options":{"numPlayers":0,"startingPlayer":0,"variantName":"No Variant","timed":false},"password":"","maxPlayers":5}"#,
+    r#"tableJoin {"tableID":7,"password":""}"#,
+    r#"tableStart {"tableID":7}"#,
+    r#"tableUnattend {"tableID":7}"#,
+    r#"getGameInfo1 {"tableID":7}"#,
+    r#"getGameInfo2 {"tableID":7}"#,
+    r#"loaded {"tableID":7}"#,
+    r#"action {"tableID":7,"type":0,"target":5}"#,
+    r#"action {"tableID":7,"type":2,"target":0,"value":3}"#,
+    r#"chat {"msg":"hello","room":"lobby"}"#,
+    r#"chatPM {"msg":"hello","recipient":"alice","room":"lobby"}"#,
+];
+
+#[test]
+fn server_frames_round_trip() {
+    for frame in SERVER_FRAMES {
+        let msg = ServerMessage::from_str(frame).unwrap_or_else(|e| panic!("{frame}: {e}"));
+        let again = ServerMessage::from_str(&msg.to_string()).unwrap();
+        assert_eq!(msg, again, "{frame}");
+    }
+}
+
+#[test]
+fn client_frames_round_trip() {
+    for frame in CLIENT_FRAMES {
+        let msg = ClientMessage::from_str(frame).unwrap_or_else(|e| panic!("{frame}: {e}"));
+        // We don't add anything the server didn't send us, so these should match exactly
+        assert_eq!(&msg.to_string(), frame);
+    }
+}
+
+#[test]
+fn game_actions_are_typed() {
+    let msg = ServerMessage::from_str(SERVER_FRAMES[15]).unwrap();
+    let ServerMessage::GameAction(msg) = msg else {
+        panic!("expected a gameAction, got {msg:?}");
+    };
+    assert_eq!(
+        msg.action,
+        GameAction::Clue {
+            clue: Clue {
+                typ: ClueType::Color,
+                value: 3,
+            },
+            giver: 0,
+            list: vec![5, 7],
+            target: 1,
+            turn: 0,
+        }
+    );
+
+    let msg = ServerMessage::from_str(SERVER_FRAMES[14]).unwrap();
+    let ServerMessage::GameActionList(msg) = msg else {
+        panic!("expected a gameActionList, got {msg:?}");
+    };
+    assert_eq!(
+        msg.list[1],
+        GameAction::Draw {
+            player_index: 1,
+            order: 5,
+            suit_index: -1,
+            rank: -1,
+        }
+    );
+    assert_eq!(card_identity(-1, -1), None);
+}
+
+#[test]
+fn unknown_commands_are_rejected() {
+    assert!(matches!(
+        ServerMessage::from_str(r#"somethingNew {"a":1}"#),
+        Err(ProtocolError::UnknownCommand(c)) if c == "somethingNew"
+    ));
+    assert!(matches!(
+        ServerMessage::from_str(""),
+        Err(ProtocolError::NoCommand)
+    ));
+}
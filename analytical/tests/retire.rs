@@ -0,0 +1,102 @@
+mod common;
+
+use analytical::{
+    Brain, CardId, Color, ColorFlags, GameEvent, GameState, GameStateBuilder, Hint, NumberFlags,
+    PlayerHand, RetireReason,
+};
+use common::{clue, fours, hand, replay, unknown_hand};
+
+// Player 1 has just given us a clue, and it's our turn
+fn clued(builder: GameStateBuilder, hint: Hint) -> (Brain, GameState) {
+    let mut state = builder.hint_count(1).current_player(1).build().unwrap();
+    let mut brain = Brain::new();
+
+    clue(
+        &mut brain,
+        &mut state,
+        GameEvent::Hint(1, 0, hint, vec![CardId(0)]),
+    );
+    assert!(!brain.thoughts_for(CardId(0)).is_empty());
+    (brain, state)
+}
+
+#[test]
+fn thoughts_about_cards_that_left_our_hand_are_retired() {
+    let builder = GameState::builder()
+        .player_hand(unknown_hand())
+        .teammate_hand(fours(5))
+        .deck(vec![CardId(10)]);
+    let (mut brain, mut state) = clued(builder, Hint::Number(1));
+
+    replay(
+        &mut brain,
+        &mut state,
+        &[
+            GameEvent::Play(0, CardId(0), Color::Red, 1),
+            GameEvent::Draw(0, CardId(10), None),
+            GameEvent::Turn(2, 1),
+            GameEvent::Discard(1, CardId(5), Color::Blue, 4),
+            GameEvent::Turn(3, 0),
+        ],
+    );
+    brain.play(&state).unwrap();
+
+    let retired = brain.retired();
+    assert_eq!(retired.len(), 1);
+    assert_eq!(retired[0].thought, "play");
+    assert_eq!(retired[0].card, CardId(0));
+    assert_eq!(retired[0].reason, RetireReason::LeftHand);
+    assert!(brain.thoughts_for(CardId(0)).is_empty());
+}
+
+// We already know our newest card is a 2, and the clue tells us it's red. Two
+// red 1s are already gone, so once player 1 discards the last one it can never
+// be played.
+#[test]
+fn thoughts_about_dead_cards_are_retired() {
+    let builder = GameState::builder()
+        .player_hand(PlayerHand::new(
+            (0..5)
+                .map(|i| match i {
+                    0 => (CardId(i), ColorFlags::all(), NumberFlags::Two, false),
+                    _ => (CardId(i), ColorFlags::all(), NumberFlags::all(), false),
+                })
+                .collect(),
+        ))
+        .teammate_hand(hand(
+            5,
+            &[
+                (Color::Red, 1),
+                (Color::Blue, 4),
+                (Color::Yellow, 4),
+                (Color::Green, 4),
+                (Color::Purple, 4),
+            ],
+        ))
+        .discarded(CardId(10), Color::Red, 1)
+        .discarded(CardId(11), Color::Red, 1)
+        .deck(vec![CardId(12), CardId(13)]);
+    let (mut brain, mut state) = clued(builder, Hint::Color(Color::Red));
+
+    replay(
+        &mut brain,
+        &mut state,
+        &[
+            GameEvent::Discard(0, CardId(4), Color::Blue, 3),
+            GameEvent::Draw(0, CardId(12), None),
+            GameEvent::Turn(2, 1),
+            GameEvent::Discard(1, CardId(5), Color::Red, 1),
+            GameEvent::Draw(1, CardId(13), Some((Color::Blue, 5))),
+            GameEvent::Turn(3, 0),
+        ],
+    );
+    let trace = brain.play_with_trace(&state).unwrap();
+
+    assert_eq!(trace.retired.len(), 1);
+    assert_eq!(trace.retired[0].card, CardId(0));
+    assert_eq!(trace.retired[0].reason, RetireReason::Trash);
+    assert!(brain.thoughts_for(CardId(0)).is_empty());
+    // Only the thought about the dead card was dropped
+    assert!(trace.polled.iter().any(|p| p.thought == "early game"));
+    assert!(trace.polled.iter().all(|p| p.card != Some(CardId(0))));
+}
@@ -0,0 +1,119 @@
+mod common;
+
+use analytical::{
+    Action, CardId, Color, GameState, GameStateBuilder, GameStatus, Hint, IllegalAction, Outcome,
+    MAX_SCORE,
+};
+use common::{fours, unknown_hand};
+
+// Our own cards are red 1, red 2, red 3, blue 1 and red 5, from newest to oldest
+fn builder() -> GameStateBuilder {
+    [
+        (Color::Red, 1),
+        (Color::Red, 2),
+        (Color::Red, 3),
+        (Color::Blue, 1),
+        (Color::Red, 5),
+    ]
+    .into_iter()
+    .enumerate()
+    .fold(
+        GameState::builder()
+            .player_hand(unknown_hand())
+            .teammate_hand(fours(5)),
+        |builder, (i, (c, n))| builder.hidden(CardId(i), c, n),
+    )
+}
+
+#[test]
+fn playing_in_order_scores() {
+    let mut state = builder().hint_count(7).build().unwrap();
+
+    assert_eq!(
+        state.apply(Action::Play(CardId(0))),
+        Ok(Outcome::Played(CardId(0), Color::Red, 1))
+    );
+    assert_eq!(state.score(), 1);
+    assert_eq!(state.status(), GameStatus::InProgress);
+}
+
+#[test]
+fn playing_a_five_gives_a_hint_back() {
+    let mut state = GameState::builder()
+        .player_hand(unknown_hand())
+        .teammate_hand(fours(5))
+        .played(CardId(20), Color::Red, 1)
+        .played(CardId(21), Color::Red, 2)
+        .played(CardId(22), Color::Red, 3)
+        .played(CardId(23), Color::Red, 4)
+        .hidden(CardId(4), Color::Red, 5)
+        .hint_count(3)
+        .build()
+        .unwrap();
+
+    state.apply(Action::Play(CardId(4))).unwrap();
+    assert_eq!(state.score(), 5);
+    assert_eq!(state.hint_count(), 4);
+}
+
+#[test]
+fn third_strike_loses_everything() {
+    let mut state = builder()
+        .played(CardId(20), Color::Blue, 1)
+        .strikes(2)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        state.apply(Action::Play(CardId(1))),
+        Ok(Outcome::Bombed(CardId(1), Color::Red, 2))
+    );
+    assert_eq!(state.strikes(), 3);
+    assert_eq!(state.status(), GameStatus::LostToStrikes(0));
+    assert_eq!(
+        state.apply(Action::Play(CardId(0))),
+        Err(IllegalAction::GameOver)
+    );
+}
+
+#[test]
+fn hints_run_out_and_fill_up() {
+    let mut state = builder().hint_count(1).build().unwrap();
+
+    assert_eq!(
+        state.apply(Action::Hint(0, Hint::Color(Color::Red))),
+        Ok(Outcome::Hinted(1, vec![CardId(8)]))
+    );
+    assert_eq!(state.hint_count(), 0);
+    // The teammate can't hint us back
+    assert_eq!(
+        state.check(Action::Hint(0, Hint::Number(1))),
+        Err(IllegalAction::NoHintsLeft)
+    );
+
+    let full = builder().build().unwrap();
+    assert_eq!(
+        full.check(Action::Discard(CardId(0))),
+        Err(IllegalAction::HintsFull)
+    );
+}
+
+#[test]
+fn everyone_gets_one_more_turn_after_the_last_draw() {
+    let mut state = builder()
+        .deck(vec![CardId(10)])
+        .hidden(CardId(10), Color::Green, 1)
+        .build()
+        .unwrap();
+
+    state.apply(Action::Play(CardId(0))).unwrap();
+    assert_eq!(state.cards_left(), 0);
+    assert_eq!(state.turns_left(), Some(2));
+    state.apply(Action::Hint(0, Hint::Number(1))).unwrap();
+    assert_eq!(state.turns_left(), Some(1));
+    assert_eq!(state.status(), GameStatus::InProgress);
+    state.apply(Action::Play(CardId(1))).unwrap();
+
+    assert_eq!(state.status(), GameStatus::EndedByDeck(2));
+    assert!(state.score() < MAX_SCORE);
+}
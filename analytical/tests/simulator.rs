@@ -0,0 +1,35 @@
+use analytical::{GameStatus, Simulator};
+
+// Every seat count, over enough seeds that the thought queue runs dry somewhere.
+// `run` stops with an error at the first illegal action, so every game has to
+// be played through legally.
+#[test]
+fn brains_finish_whole_games() {
+    for players in 2..=6 {
+        let mut strikeouts = 0;
+        let mut total = 0;
+        for seed in 0..20 {
+            let record = Simulator::new(players, seed)
+                .unwrap()
+                .run()
+                .unwrap_or_else(|e| panic!("{players} players, seed {seed} was illegal: {e}"));
+            match record.status() {
+                GameStatus::InProgress => panic!("{players} players, seed {seed} never finished"),
+                GameStatus::LostToStrikes(_) => strikeouts += 1,
+                GameStatus::Won(_) | GameStatus::EndedByDeck(_) => {}
+            }
+            total += record.score();
+        }
+        // Loose enough to leave room for tuning, but catches the brains bombing
+        // their way out of most games
+        assert!(
+            strikeouts <= 6,
+            "{players} players struck out {strikeouts} times in 20 games"
+        );
+        assert!(
+            total >= 20 * 7,
+            "{players} players only averaged {} points",
+            total as f64 / 20.0
+        );
+    }
+}
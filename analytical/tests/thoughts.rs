@@ -0,0 +1,61 @@
+mod common;
+
+use analytical::{Action, Brain, CardId, Color, GameEvent, Hint, Intent};
+
+#[test]
+fn index_follows_the_queue() {
+    let mut state = common::state(1);
+    let mut brain = Brain::new();
+    assert!(brain.cards_with_play_intent().is_empty());
+
+    let one = GameEvent::Hint(1, 0, Hint::Number(1), vec![CardId(0)]);
+    common::clue(&mut brain, &mut state, one);
+
+    assert_eq!(brain.cards_with_play_intent(), vec![CardId(0)]);
+    let beliefs = brain.thoughts_for(CardId(0));
+    assert_eq!(beliefs.len(), 1);
+    assert_eq!(beliefs[0].intent, Intent::Play);
+    assert!(brain.thoughts_for(CardId(1)).is_empty());
+
+    // Playing it finishes the thought, so there's nothing left to believe
+    brain.play(&state).unwrap();
+    assert!(brain.thoughts_for(CardId(0)).is_empty());
+    assert!(brain.cards_with_play_intent().is_empty());
+}
+
+// A 5 on our chop is kept safe rather than played, so it's still there after our turn
+#[test]
+fn index_keeps_thoughts_that_are_still_pending() {
+    let mut state = common::state(1);
+    let mut brain = Brain::new();
+
+    let five = GameEvent::Hint(1, 0, Hint::Number(5), vec![CardId(4)]);
+    common::clue(&mut brain, &mut state, five);
+
+    for _ in 0..2 {
+        let beliefs = brain.thoughts_for(CardId(4));
+        assert_eq!(beliefs.len(), 1);
+        assert_eq!(beliefs[0].intent, Intent::Save);
+        assert_eq!(beliefs[0].number, Some(5));
+        brain.play(&state).unwrap();
+    }
+}
+
+// Both clues are play clues, so whichever we were given first gets played first
+#[test]
+fn thoughts_with_the_same_priority_are_polled_in_order() {
+    let mut state = common::state(2);
+    let mut brain = Brain::new();
+
+    common::replay(
+        &mut brain,
+        &mut state,
+        &[
+            GameEvent::Hint(1, 0, Hint::Color(Color::Red), vec![CardId(3)]),
+            GameEvent::Hint(1, 0, Hint::Color(Color::Blue), vec![CardId(0)]),
+            GameEvent::Turn(1, 0),
+        ],
+    );
+
+    assert_eq!(brain.play(&state).unwrap(), Action::Play(CardId(3)));
+}
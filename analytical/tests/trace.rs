@@ -0,0 +1,52 @@
+mod common;
+
+use analytical::{Action, Brain, CardId, GameEvent, GameState, Hint, Poll};
+use common::state;
+
+// We've been told our newest card is a 1, with nothing played yet
+fn clued_brain() -> (Brain, GameState) {
+    let mut state = state(1);
+    let mut brain = Brain::new();
+    let one = GameEvent::Hint(1, 0, Hint::Number(1), vec![CardId(0)]);
+    common::clue(&mut brain, &mut state, one);
+
+    (brain, state)
+}
+
+#[test]
+fn trace_records_the_thought_that_decided() {
+    let (mut brain, state) = clued_brain();
+    let trace = brain.play_with_trace(&state).unwrap();
+
+    assert_eq!(trace.action, Action::Play(CardId(0)));
+    assert!(!trace.fallback);
+    let play = trace
+        .polled
+        .iter()
+        .find(|p| p.thought == "play")
+        .expect("the play thought was polled");
+    assert_eq!(play.card, Some(CardId(0)));
+    assert_eq!(play.result, Poll::Finished(Some(Action::Play(CardId(0)))));
+    assert!(!play.steps.is_empty());
+    assert!(trace.to_string().contains("play thought about 0"));
+}
+
+#[test]
+fn trace_records_falling_back() {
+    let mut brain = Brain::new();
+    let trace = brain.play_with_trace(&state(0)).unwrap();
+
+    assert!(trace.fallback);
+    assert_eq!(trace.action, Action::Discard(CardId(4)));
+}
+
+#[cfg(all(feature = "serde", feature = "serde_json"))]
+#[test]
+fn trace_serialises_to_json() {
+    let (mut brain, state) = clued_brain();
+    let trace = brain.play_with_trace(&state).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&trace.to_json().unwrap()).unwrap();
+
+    assert_eq!(json["action"], serde_json::json!({ "Play": 0 }));
+    assert_eq!(json["fallback"], serde_json::json!(false));
+}
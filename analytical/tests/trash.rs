@@ -0,0 +1,109 @@
+mod common;
+
+use analytical::{CardId, Color, ColorFlags, GameState, NumberFlags, PlayerHand, TeammateHand};
+
+// Red 1 is played, both blue 2s are gone, and the teammate has a touched green 3
+fn state() -> GameState {
+    GameState::builder()
+        .player_hand(common::unknown_hand())
+        .teammate_hand(TeammateHand::new(vec![
+            (CardId(5), Color::Green, 3, true),
+            (CardId(6), Color::Yellow, 4, false),
+            (CardId(7), Color::Green, 3, false),
+            (CardId(8), Color::Red, 1, false),
+            (CardId(9), Color::Purple, 5, false),
+        ]))
+        .played(CardId(10), Color::Red, 1)
+        .discarded(CardId(11), Color::Blue, 2)
+        .discarded(CardId(12), Color::Blue, 2)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn already_played_cards_are_trash() {
+    let state = state();
+    assert!(state.is_trash(ColorFlags::Red, NumberFlags::One));
+    assert!(!state.is_trash(ColorFlags::Red, NumberFlags::One | NumberFlags::Two));
+    assert!(CardId(8).resolve(&state).is_trash(&state));
+}
+
+#[test]
+fn unreachable_cards_are_trash() {
+    let state = state();
+    assert!(state.is_trash(ColorFlags::Blue, NumberFlags::Three | NumberFlags::Five));
+    assert!(!state.is_trash(ColorFlags::Blue, NumberFlags::One));
+    assert!(!state.is_trash(ColorFlags::all(), NumberFlags::all()));
+}
+
+#[test]
+fn duplicates_in_the_same_hand_are_not_trash() {
+    let state = state();
+    // Nothing about green 3 on its own makes it trash
+    assert!(!state.is_trash(ColorFlags::Green, NumberFlags::Three));
+    // Both copies are in the same hand, so neither is trash to the teammate
+    assert!(!CardId(7).resolve(&state).is_trash(&state));
+    assert!(!CardId(5).resolve(&state).is_trash(&state));
+    assert!(!CardId(9).resolve(&state).is_trash(&state));
+}
+
+// Player 1 has a touched red 3, and we know our newest card is red 3 as well
+fn duplicates(touched: bool) -> GameState {
+    GameState::builder()
+        .player_hand(PlayerHand::new(
+            (0..5)
+                .map(|i| match i {
+                    0 => (CardId(i), ColorFlags::Red, NumberFlags::Three, touched),
+                    _ => (CardId(i), ColorFlags::all(), NumberFlags::all(), false),
+                })
+                .collect(),
+        ))
+        .teammate_hand(TeammateHand::new(vec![
+            (CardId(5), Color::Red, 3, true),
+            (CardId(6), Color::Blue, 4, false),
+            (CardId(7), Color::Yellow, 4, false),
+            (CardId(8), Color::Green, 4, false),
+            (CardId(9), Color::Purple, 4, false),
+        ]))
+        .build()
+        .unwrap()
+}
+
+// Ours was drawn first, so it's the one kept
+#[test]
+fn only_the_first_touched_copy_is_kept() {
+    let state = duplicates(true);
+    assert!(!CardId(0).resolve(&state).is_trash(&state));
+    assert!(CardId(5).resolve(&state).is_trash(&state));
+}
+
+#[test]
+fn untouched_copies_of_touched_cards_are_trash() {
+    let state = duplicates(false);
+    assert!(CardId(0).resolve(&state).is_trash(&state));
+    assert!(!CardId(5).resolve(&state).is_trash(&state));
+}
+
+// Two teammates holding the same touched card keep one of them
+#[test]
+fn teammates_keep_one_of_their_duplicates() {
+    let state = GameState::builder()
+        .player_hand(common::unknown_hand())
+        .teammate_hand(TeammateHand::new(vec![
+            (CardId(5), Color::Red, 3, true),
+            (CardId(6), Color::Blue, 4, false),
+            (CardId(7), Color::Yellow, 4, false),
+            (CardId(8), Color::Green, 4, false),
+        ]))
+        .teammate_hand(TeammateHand::new(vec![
+            (CardId(9), Color::Purple, 4, false),
+            (CardId(10), Color::Red, 3, true),
+            (CardId(11), Color::Blue, 3, false),
+            (CardId(12), Color::Yellow, 3, false),
+        ]))
+        .build()
+        .unwrap();
+
+    assert!(!CardId(5).resolve(&state).is_trash(&state));
+    assert!(CardId(10).resolve(&state).is_trash(&state));
+}